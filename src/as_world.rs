@@ -0,0 +1,95 @@
+use bevy_app::{App, SubApp, SubApps};
+use bevy_ecs::world::World;
+
+use crate::test_app::TestApp;
+
+/// Anything the crate's traits can operate on: [`World`] itself, or
+/// something owning one like [`App`], [`SubApp`] and [`TestApp`].
+///
+/// For apps this is the main world.
+pub trait AsWorld {
+    fn as_world(&self) -> &World;
+    fn as_world_mut(&mut self) -> &mut World;
+
+    /// the app's sub-apps, for traits working on another world like the
+    /// render world; a world or sub-app on its own has none
+    fn as_sub_apps(&self) -> Option<&SubApps> {
+        None
+    }
+    fn as_sub_apps_mut(&mut self) -> Option<&mut SubApps> {
+        None
+    }
+}
+
+impl AsWorld for World {
+    fn as_world(&self) -> &World {
+        self
+    }
+    fn as_world_mut(&mut self) -> &mut World {
+        self
+    }
+}
+
+impl AsWorld for App {
+    fn as_world(&self) -> &World {
+        self.world()
+    }
+    fn as_world_mut(&mut self) -> &mut World {
+        self.world_mut()
+    }
+    fn as_sub_apps(&self) -> Option<&SubApps> {
+        Some(self.sub_apps())
+    }
+    fn as_sub_apps_mut(&mut self) -> Option<&mut SubApps> {
+        Some(self.sub_apps_mut())
+    }
+}
+
+impl AsWorld for SubApp {
+    fn as_world(&self) -> &World {
+        self.world()
+    }
+    fn as_world_mut(&mut self) -> &mut World {
+        self.world_mut()
+    }
+}
+
+impl AsWorld for TestApp {
+    fn as_world(&self) -> &World {
+        self.world()
+    }
+    fn as_world_mut(&mut self) -> &mut World {
+        self.world_mut()
+    }
+    fn as_sub_apps(&self) -> Option<&SubApps> {
+        Some(self.sub_apps())
+    }
+    fn as_sub_apps_mut(&mut self) -> Option<&mut SubApps> {
+        Some(self.sub_apps_mut())
+    }
+}
+
+/// An [`App`] or a [`TestApp`], for the traits that reach past the main
+/// world, which a [`World`] or [`SubApp`] on its own doesn't have.
+pub trait AsApp: AsWorld {
+    fn as_app(&self) -> &App;
+    fn as_app_mut(&mut self) -> &mut App;
+}
+
+impl AsApp for App {
+    fn as_app(&self) -> &App {
+        self
+    }
+    fn as_app_mut(&mut self) -> &mut App {
+        self
+    }
+}
+
+impl AsApp for TestApp {
+    fn as_app(&self) -> &App {
+        &self.0
+    }
+    fn as_app_mut(&mut self) -> &mut App {
+        &mut self.0
+    }
+}
@@ -1,3 +1,11 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "color")]
+use bevy_color::{Color, LinearRgba};
+use bevy_internal::{
+    math::{Quat, Vec2, Vec3},
+    transform::components::Transform,
+};
 use speculoos::{AssertionFailure, Spec};
 
 pub trait IsContainedIn<T>
@@ -22,3 +30,217 @@ where
         }
     }
 }
+
+/// A value made of floats that can be compared one by one.
+pub trait FloatComponents: Copy + Debug {
+    fn components(&self) -> Vec<(&'static str, f32)>;
+
+    /// the equivalent of `self` closest to `other`, e.g. `-q` for a quaternion
+    fn aligned_with(self, _other: &Self) -> Self {
+        self
+    }
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl FloatComponents for Vec2 {
+    fn components(&self) -> Vec<(&'static str, f32)> {
+        vec![("x", self.x), ("y", self.y)]
+    }
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl FloatComponents for Vec3 {
+    fn components(&self) -> Vec<(&'static str, f32)> {
+        vec![("x", self.x), ("y", self.y), ("z", self.z)]
+    }
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl FloatComponents for Quat {
+    fn components(&self) -> Vec<(&'static str, f32)> {
+        vec![("x", self.x), ("y", self.y), ("z", self.z), ("w", self.w)]
+    }
+
+    fn aligned_with(self, other: &Self) -> Self {
+        // `q` and `-q` are the same rotation
+        if self.dot(*other) < 0.0 { -self } else { self }
+    }
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl FloatComponents for Transform {
+    fn components(&self) -> Vec<(&'static str, f32)> {
+        let Self {
+            translation: t,
+            rotation: r,
+            scale: s,
+        } = self;
+        vec![
+            ("translation.x", t.x),
+            ("translation.y", t.y),
+            ("translation.z", t.z),
+            ("rotation.x", r.x),
+            ("rotation.y", r.y),
+            ("rotation.z", r.z),
+            ("rotation.w", r.w),
+            ("scale.x", s.x),
+            ("scale.y", s.y),
+            ("scale.z", s.z),
+        ]
+    }
+
+    fn aligned_with(self, other: &Self) -> Self {
+        Self {
+            rotation: self.rotation.aligned_with(&other.rotation),
+            ..self
+        }
+    }
+}
+
+/// compared in linear RGBA, whatever the color space
+#[cfg(feature = "color")]
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl FloatComponents for Color {
+    fn components(&self) -> Vec<(&'static str, f32)> {
+        let LinearRgba {
+            red,
+            green,
+            blue,
+            alpha,
+        } = (*self).into();
+        vec![
+            ("red", red),
+            ("green", green),
+            ("blue", blue),
+            ("alpha", alpha),
+        ]
+    }
+}
+
+pub trait IsCloseTo<T> {
+    fn is_close_to(&mut self, expected: T, epsilon: f32);
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<T: FloatComponents> IsCloseTo<T> for Spec<'_, T> {
+    fn is_close_to(&mut self, expected: T, epsilon: f32) {
+        let subject = self.subject.aligned_with(&expected);
+        let differences = subject
+            .components()
+            .into_iter()
+            .zip(expected.components())
+            .filter(|((_, actual), (_, expected))| {
+                let difference = (actual - expected).abs();
+                // NaN is never close to anything
+                difference.is_nan() || difference > epsilon
+            })
+            .map(|((name, actual), (_, expected))| {
+                format!(
+                    "{name}: {actual} (expected {expected}, diff {})",
+                    actual - expected
+                )
+            })
+            .collect::<Vec<_>>();
+        if !differences.is_empty() {
+            AssertionFailure::from_spec(self)
+                .with_expected(format!("<{expected:?}> within <{epsilon}>"))
+                .with_actual(format!(
+                    "<{:?}>, differing in\n\t{}",
+                    self.subject,
+                    differences.join("\n\t")
+                ))
+                .fail();
+        }
+    }
+}
+
+/// A value with a length that should be 1.
+pub trait Normalizable: Debug {
+    fn length(&self) -> f32;
+    fn is_normalized(&self) -> bool;
+}
+
+macro_rules! impl_normalizable {
+    ($($ty:ty),*) => {
+        $(#[cfg_attr(coverage_nightly, coverage(off))]
+        impl Normalizable for $ty {
+            fn length(&self) -> f32 {
+                <$ty>::length(*self)
+            }
+            fn is_normalized(&self) -> bool {
+                <$ty>::is_normalized(*self)
+            }
+        })*
+    };
+}
+
+impl_normalizable!(Vec2, Vec3, Quat);
+
+pub trait IsNormalized {
+    fn is_normalized(&mut self);
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl<T: Normalizable> IsNormalized for Spec<'_, T> {
+    fn is_normalized(&mut self) {
+        let subject = self.subject;
+        if !subject.is_normalized() {
+            AssertionFailure::from_spec(self)
+                .with_expected(format!("<{subject:?}> to be normalized"))
+                .with_actual(format!("a length of <{}>", subject.length()))
+                .fail();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use rstest::rstest;
+    use speculoos::assert_that;
+
+    use super::*;
+
+    #[rstest]
+    fn test_is_close_to() {
+        assert_that!(Vec2::new(1.0, 2.0)).is_close_to(Vec2::new(1.0005, 1.9995), 1e-3);
+        assert_that!(Vec3::X).is_close_to(Vec3::new(1.0, 0.0, 1e-7), 1e-6);
+        #[cfg(feature = "color")]
+        assert_that!(Color::WHITE).is_close_to(Color::srgb(1.0, 1.0, 1.0), 1e-6);
+        assert_that!(Transform::from_xyz(1.0, 2.0, 3.0))
+            .is_close_to(Transform::from_xyz(1.0, 2.0, 3.0 + 1e-4), 1e-3);
+    }
+
+    #[rstest]
+    fn test_is_close_to_equivalent_quat() {
+        let rotation = Quat::from_rotation_z(FRAC_PI_2);
+        assert_that!(rotation).is_close_to(-rotation, 1e-6);
+        assert_that!(Transform::from_rotation(rotation))
+            .is_close_to(Transform::from_rotation(-rotation), 1e-6);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "y: 2 (expected 2.1, diff -0.")]
+    fn test_is_close_to_shows_differences() {
+        assert_that!(Vec3::new(1.0, 2.0, 3.0)).is_close_to(Vec3::new(1.0, 2.1, 3.0), 1e-3);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "differing in")]
+    fn test_is_close_to_nan() {
+        assert_that!(Vec2::new(f32::NAN, 0.0)).is_close_to(Vec2::ZERO, 1.0);
+    }
+
+    #[rstest]
+    fn test_is_normalized() {
+        assert_that!(Vec3::new(0.6, 0.8, 0.0)).is_normalized();
+        assert_that!(Quat::from_rotation_x(1.0)).is_normalized();
+    }
+
+    #[rstest]
+    #[should_panic(expected = "a length of <2>")]
+    fn test_is_normalized_fails() {
+        assert_that!(Vec2::new(0.0, 2.0)).is_normalized();
+    }
+}
@@ -0,0 +1,345 @@
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{
+    AssetApp, AssetLoadError, AssetPlugin,
+    io::{
+        AssetReader, AssetReaderError, AssetSourceBuilder, AssetSourceEvent, AssetSourceId,
+        AssetWatcher, PathStream, Reader,
+        memory::{Dir, MemoryAssetReader, MemoryAssetWriter, Value},
+    },
+};
+use bevy_ecs::resource::Resource;
+use bevy_internal::tasks::tick_global_task_pools_on_main_thread;
+
+type SendSourceEvent = Box<dyn Fn(AssetSourceEvent) + Send + Sync>;
+
+/// The in-memory directory backing the default asset source.
+#[derive(Clone, Default, Resource)]
+pub struct TestAssetSource {
+    pub dir: Dir,
+    /// every path added or read so far
+    paths: Arc<Mutex<BTreeSet<PathBuf>>>,
+    /// what a file watcher would use to tell the `AssetServer` about changes
+    events: Arc<Mutex<Option<SendSourceEvent>>>,
+}
+
+impl Debug for TestAssetSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestAssetSource")
+            .field("dir", &self.dir)
+            .field("paths", &self.paths)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TestAssetSource {
+    pub fn insert(&self, path: &Path, bytes: impl Into<Value>) {
+        self.dir.insert_asset(path, bytes);
+        self.record(path);
+    }
+
+    /// Replaces the bytes at `path` and tells the `AssetServer`, like a file
+    /// watcher would.
+    pub fn modify(&self, path: &Path, bytes: impl Into<Value>) {
+        self.insert(path, bytes);
+        if let Some(send) = self.events.lock().unwrap().as_ref() {
+            send(AssetSourceEvent::ModifiedAsset(path.to_path_buf()));
+        }
+    }
+
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.paths.lock().unwrap().iter().cloned().collect()
+    }
+
+    fn record(&self, path: &Path) {
+        self.paths.lock().unwrap().insert(path.to_path_buf());
+    }
+}
+
+/// a [`MemoryAssetReader`] recording the paths it's asked for
+struct RecordingReader {
+    reader: MemoryAssetReader,
+    source: TestAssetSource,
+}
+
+impl AssetReader for RecordingReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.source.record(path);
+        self.reader.read(path).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.source.record(path);
+        self.reader.read_meta(path).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        self.reader.read_directory(path).await
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        self.reader.is_directory(path).await
+    }
+}
+
+/// keeps the `AssetServer` watching, the changes come from [`TestAssetSource::modify`]
+struct TestAssetWatcher;
+
+impl AssetWatcher for TestAssetWatcher {}
+
+/// Adds `AssetPlugin` reading from [`TestAssetSource`] instead of the
+/// `assets/` folder, so tests don't depend on the working directory.
+#[derive(Debug, Default)]
+pub struct TestAssetsPlugin;
+
+impl Plugin for TestAssetsPlugin {
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn build(&self, app: &mut App) {
+        let source = TestAssetSource::default();
+        let reader_source = source.clone();
+        let writer_dir = source.dir.clone();
+        let events = source.events.clone();
+        app.insert_resource(source)
+            .register_asset_source(
+                AssetSourceId::Default,
+                AssetSourceBuilder::new(move || {
+                    Box::new(RecordingReader {
+                        reader: MemoryAssetReader {
+                            root: reader_source.dir.clone(),
+                        },
+                        source: reader_source.clone(),
+                    })
+                })
+                .with_writer(move |_| {
+                    Some(Box::new(MemoryAssetWriter {
+                        root: writer_dir.clone(),
+                    }))
+                })
+                .with_watcher(move |sender| {
+                    *events.lock().unwrap() = Some(Box::new(move |event| {
+                        // the server may be gone already
+                        let _ = sender.try_send(event);
+                    }));
+                    Some(Box::new(TestAssetWatcher))
+                }),
+            )
+            .add_plugins(AssetPlugin {
+                watch_for_changes_override: Some(true),
+                use_asset_processor_override: Some(false),
+                ..Default::default()
+            });
+    }
+}
+
+/// Why waiting for an asset failed.
+#[derive(Clone, Debug)]
+pub enum AssetWaitError {
+    Failed {
+        path: String,
+        error: Arc<AssetLoadError>,
+    },
+    StillLoading {
+        paths: Vec<String>,
+        frames: u32,
+    },
+}
+
+impl Display for AssetWaitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed { path, error } => write!(f, "failed to load `{path}`: {error}"),
+            Self::StillLoading { paths, frames } => write!(
+                f,
+                "still loading after {frames} frame(s): `{}`",
+                paths.join("`, `")
+            ),
+        }
+    }
+}
+
+impl Error for AssetWaitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Failed { error, .. } => Some(error.as_ref()),
+            Self::StillLoading { .. } => None,
+        }
+    }
+}
+
+/// Runs the loading tasks that are ready, like the app does once per update.
+///
+/// With bevy's `multi_threaded` feature they run on other threads instead, and
+/// may still be reading when the next update starts.
+pub(crate) fn let_tasks_run() {
+    tick_global_task_pools_on_main_thread();
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::reflect::TypePath;
+    use bevy_asset::{
+        Asset, AssetEvent, AssetLoader, AssetServer, Assets, Handle, LoadContext, io::Reader,
+    };
+    use bevy_ecs::message::Messages;
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::asset_test_app, test_app::TestApp, traits::TestAssets};
+
+    #[derive(Asset, TypePath, Debug)]
+    struct Text(String);
+
+    #[derive(Default, TypePath)]
+    struct TextLoader;
+
+    impl AssetLoader for TextLoader {
+        type Asset = Text;
+        type Settings = ();
+        type Error = std::io::Error;
+
+        async fn load(
+            &self,
+            reader: &mut dyn Reader,
+            _settings: &(),
+            _load_context: &mut LoadContext<'_>,
+        ) -> Result<Text, Self::Error> {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            String::from_utf8(bytes)
+                .map(Text)
+                .map_err(std::io::Error::other)
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    fn text_assets(app: &mut App) {
+        app.init_asset::<Text>().init_asset_loader::<TextLoader>();
+    }
+
+    #[rstest]
+    fn test_update_until_loaded(
+        #[from(asset_test_app)]
+        #[with(text_assets)]
+        mut app: TestApp,
+    ) {
+        app.add_test_asset("greetings/hello.txt", b"hello");
+        let handle: Handle<Text> = app
+            .world()
+            .resource::<AssetServer>()
+            .load("greetings/hello.txt");
+
+        let load_state = app.update_until_loaded(&handle, 100);
+
+        assert_that!(load_state.is_loaded()).is_true();
+        let assets = app.world().resource::<Assets<Text>>();
+        assert_that!(assets.get(&handle).map(|text| text.0.as_str())).is_equal_to(Some("hello"));
+    }
+
+    #[rstest]
+    fn test_update_until_loaded_missing_asset(
+        #[from(asset_test_app)]
+        #[with(text_assets)]
+        mut app: TestApp,
+    ) {
+        let handle: Handle<Text> = app.world().resource::<AssetServer>().load("missing.txt");
+
+        let load_state = app.update_until_loaded(&handle, 100);
+
+        assert_that!(load_state.is_failed()).is_true();
+    }
+
+    #[rstest]
+    fn test_load_and_wait(
+        #[from(asset_test_app)]
+        #[with(text_assets)]
+        mut app: TestApp,
+    ) {
+        app.add_test_asset("hello.txt", b"hello");
+
+        let handle = app.load_and_wait::<Text>("hello.txt", 100).unwrap();
+
+        let assets = app.world().resource::<Assets<Text>>();
+        assert_that!(assets.get(&handle).map(|text| text.0.as_str())).is_equal_to(Some("hello"));
+    }
+
+    #[rstest]
+    fn test_load_and_wait_invalid_asset(
+        #[from(asset_test_app)]
+        #[with(text_assets)]
+        mut app: TestApp,
+    ) {
+        app.add_test_asset("invalid.txt", vec![0xff, 0xfe]);
+
+        let error = app.load_and_wait::<Text>("invalid.txt", 100).unwrap_err();
+
+        assert_that!(error.to_string()).starts_with("failed to load `invalid.txt`: ");
+    }
+
+    #[rstest]
+    fn test_wait_for_all_assets(
+        #[from(asset_test_app)]
+        #[with(text_assets)]
+        mut app: TestApp,
+    ) {
+        app.add_test_asset("a.txt", b"a")
+            .add_test_asset("b.txt", b"b");
+        let server = app.world().resource::<AssetServer>();
+        let handles: Vec<Handle<Text>> = vec![server.load("a.txt"), server.load("b.txt")];
+
+        assert_that!(app.wait_for_all_assets(100)).is_ok();
+
+        let server = app.world().resource::<AssetServer>();
+        assert_that!(handles.iter().all(|handle| server.is_loaded(handle))).is_true();
+    }
+
+    #[rstest]
+    fn test_wait_for_all_assets_missing_asset(
+        #[from(asset_test_app)]
+        #[with(text_assets)]
+        mut app: TestApp,
+    ) {
+        let _handle: Handle<Text> = app.world().resource::<AssetServer>().load("missing.txt");
+
+        let error = app.wait_for_all_assets(100).unwrap_err();
+
+        assert_that!(error.to_string()).starts_with("failed to load `missing.txt`: ");
+    }
+
+    #[rstest]
+    fn test_modify_test_asset(
+        #[from(asset_test_app)]
+        #[with(text_assets)]
+        mut app: TestApp,
+    ) {
+        app.add_test_asset("config.txt", b"slow");
+        let handle = app.load_and_wait::<Text>("config.txt", 100).unwrap();
+
+        app.modify_test_asset("config.txt", b"fast");
+
+        let id = handle.id();
+        app.within_frames(100, |app| {
+            // reloading happens on the task pools
+            let_tasks_run();
+            app.world()
+                .resource::<Messages<AssetEvent<Text>>>()
+                .iter_current_update_messages()
+                .any(|event| event.is_modified(id))
+        });
+        let assets = app.world().resource::<Assets<Text>>();
+        assert_that!(assets.get(&handle).map(|text| text.0.as_str())).is_equal_to(Some("fast"));
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+
+use bevy_app::{App, First, Main, Plugin};
+use bevy_ecs::{
+    resource::Resource,
+    schedule::{InternedScheduleLabel, ScheduleLabel, Schedules, SingleThreadedExecutor},
+    system::ResMut,
+    world::World,
+};
+
+/// Runs every schedule of the main app on the thread calling `app.update()`.
+///
+/// Thread-local hooks (log capture, error handlers) only observe what happens
+/// on the test thread, so they rely on this to see every system.
+#[derive(Debug, Default)]
+pub struct SingleThreadedExecutorPlugin;
+
+impl Plugin for SingleThreadedExecutorPlugin {
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn build(&self, app: &mut App) {
+        run_single_threaded(app.world_mut());
+    }
+}
+
+/// the schedules whose executor was replaced already
+#[derive(Default, Resource)]
+struct SingleThreaded(HashSet<InternedScheduleLabel>);
+
+pub(crate) fn run_single_threaded(world: &mut World) {
+    if world.contains_resource::<SingleThreaded>() {
+        return;
+    }
+    // these are running while `use_single_threaded_executors` runs
+    let running = [Main.intern(), First.intern()];
+    let mut schedules = world.get_resource_or_init::<Schedules>();
+    for label in running {
+        schedules
+            .entry(label)
+            .set_executor(SingleThreadedExecutor::new());
+    }
+    schedules.add_systems(First, use_single_threaded_executors);
+    world.insert_resource(SingleThreaded(running.into_iter().collect()));
+}
+
+/// schedules can be added at any time, so check them every frame
+fn use_single_threaded_executors(
+    mut schedules: ResMut<Schedules>,
+    mut single_threaded: ResMut<SingleThreaded>,
+) {
+    for (_, schedule) in schedules.iter_mut() {
+        if single_threaded.0.insert(schedule.label()) {
+            schedule.set_executor(SingleThreadedExecutor::new());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        thread::{self, ThreadId},
+    };
+
+    use bevy_app::{PostUpdate, Update};
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    #[rstest]
+    fn test_schedules_become_single_threaded(
+        #[from(minimal_test_app)]
+        #[with(SingleThreadedExecutorPlugin)]
+        mut app: TestApp,
+    ) {
+        let threads = Arc::new(Mutex::new(Vec::<ThreadId>::new()));
+        for schedule in [Update.intern(), PostUpdate.intern()] {
+            let threads = threads.clone();
+            // several systems, a multi-threaded executor would spread them
+            for _ in 0..4 {
+                let threads = threads.clone();
+                app.add_systems(schedule, move || {
+                    threads.lock().unwrap().push(thread::current().id());
+                });
+            }
+        }
+
+        app.update();
+
+        let threads = threads.lock().unwrap();
+        assert_that!(*threads).has_length(8);
+        assert_that!(threads.iter().all(|id| *id == thread::current().id())).is_true();
+    }
+}
@@ -56,6 +56,74 @@ pub fn default_test_app<P>(
     app
 }
 
+/// minimal_test_app + bevy's render and pbr plugins, without a GPU
+///
+/// `RenderPlugin` gets no backend, but meshes, materials and cameras work as
+/// usual. The render world only runs the extraction systems a test adds to
+/// it, see [`ExtractionPlugin`]. The plugins are finished already, so more
+/// can only be added through `additional_plugins`.
+///
+/// [`ExtractionPlugin`]: crate::render::ExtractionPlugin
+#[cfg(feature = "rstest")]
+#[fixture]
+pub fn headless_render_test_app<P>(
+    #[default(())] additional_plugins: impl Plugins<P>,
+    #[from(minimal_test_app)] mut app: TestApp,
+) -> TestApp {
+    use bevy_asset::AssetPlugin;
+    use bevy_image::ImagePlugin;
+    use bevy_internal::{
+        camera::CameraPlugin, core_pipeline::CorePipelinePlugin, light::LightPlugin,
+        transform::TransformPlugin,
+    };
+    use bevy_mesh::MeshPlugin;
+    use bevy_pbr::PbrPlugin;
+    use bevy_render::{RenderPlugin, settings::WgpuSettings};
+
+    use crate::render::ExtractionPlugin;
+
+    // in the order of `DefaultPlugins`
+    app.add_plugins((
+        TransformPlugin,
+        AssetPlugin::default(),
+        RenderPlugin {
+            render_creation: WgpuSettings {
+                backends: None,
+                ..default()
+            }
+            .into(),
+            ..default()
+        },
+        ImagePlugin::default(),
+        MeshPlugin,
+        CameraPlugin,
+        LightPlugin,
+        CorePipelinePlugin,
+        PbrPlugin::default(),
+    ))
+    .add_plugins(additional_plugins)
+    // last, so that no other plugin sets up the GPU side of the render world
+    .add_plugins(ExtractionPlugin);
+    app.finish();
+    app.cleanup();
+    app
+}
+
+/// minimal_test_app + assets read from memory, see [`TestAssetsPlugin`]
+///
+/// [`TestAssetsPlugin`]: crate::assets::TestAssetsPlugin
+#[cfg(feature = "trait_test_assets")]
+#[fixture]
+pub fn asset_test_app<P>(
+    #[default(())] additional_plugins: impl Plugins<P>,
+    #[from(minimal_test_app)] mut app: TestApp,
+) -> TestApp {
+    use crate::assets::TestAssetsPlugin;
+
+    app.add_plugins((TestAssetsPlugin, additional_plugins));
+    app
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -64,11 +132,11 @@ mod tests {
     use bevy_app::{App, AppExit, Plugin, Update};
     use bevy_ecs::{message::MessageWriter, schedule::IntoScheduleConfigs};
     use rstest::rstest;
-    use speculoos::assert_that;
+    use speculoos::prelude::*;
 
-    #[cfg(feature = "rstest")]
-    use crate::fixtures::default_test_app;
     use crate::fixtures::{TestApp, minimal_test_app};
+    #[cfg(feature = "rstest")]
+    use crate::fixtures::{default_test_app, headless_render_test_app};
 
     fn app_timeout_plugin(duration: Duration) -> impl Plugin {
         fn write_app_exit(mut app_exit: MessageWriter<AppExit>) {
@@ -105,4 +173,42 @@ mod tests {
             .named("AppExit within timeout")
             .is_equal_to(AppExit::Success);
     }
+
+    #[cfg(feature = "rstest")]
+    #[rstest]
+    fn test_headless_render_test_app(#[from(headless_render_test_app)] mut app: TestApp) {
+        use bevy_asset::Assets;
+        use bevy_internal::{
+            camera::{Camera, Camera3d},
+            math::primitives::Cuboid,
+            render::RenderApp,
+        };
+        use bevy_mesh::{Mesh, Mesh3d};
+        use bevy_pbr::{MeshMaterial3d, StandardMaterial};
+
+        let mesh = app
+            .world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .add(Cuboid::default());
+        let material = app
+            .world_mut()
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial::default());
+        app.world_mut()
+            .spawn((Mesh3d(mesh), MeshMaterial3d(material)));
+        let camera = app.world_mut().spawn(Camera3d::default()).id();
+
+        app.update();
+
+        assert_that!(app.world().get::<Camera>(camera).is_some())
+            .named("required components of Camera3d")
+            .is_true();
+        let render_app = app.get_sub_app(RenderApp);
+        assert_that!(render_app.is_some())
+            .named("render world for extraction")
+            .is_true();
+        assert_that!(render_app.is_some_and(|render_app| render_app.update_schedule.is_none()))
+            .named("nothing rendered without a backend")
+            .is_true();
+    }
 }
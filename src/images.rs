@@ -0,0 +1,411 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io::Cursor,
+    path::PathBuf,
+};
+#[cfg(feature = "insta")]
+use std::{fs, path::Path};
+
+use bevy_color::{Alpha, Color, Srgba};
+use bevy_image::{Image, IntoDynamicImageError, TextureAccessError};
+use bevy_internal::math::UVec2;
+use image::ImageFormat;
+use speculoos::{AssertionFailure, Spec};
+
+#[derive(Debug)]
+pub enum ImageError {
+    Size { actual: UVec2, expected: UVec2 },
+    Access(TextureAccessError),
+    Convert(IntoDynamicImageError),
+    Encode(image::ImageError),
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Size { actual, expected } => {
+                write!(f, "the image is {actual}, expected {expected}")
+            }
+            Self::Access(error) => write!(f, "can't read the pixels: {error}"),
+            Self::Convert(error) => write!(f, "can't convert the image: {error}"),
+            Self::Encode(error) => write!(f, "can't encode the image: {error}"),
+        }
+    }
+}
+
+impl Error for ImageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Size { .. } => None,
+            Self::Access(error) => Some(error),
+            Self::Convert(error) => Some(error),
+            Self::Encode(error) => Some(error),
+        }
+    }
+}
+
+/// The pixels of two images that differ, see [`compare_images`].
+#[derive(Debug)]
+pub struct ImageDiff {
+    pub mismatched: Vec<UVec2>,
+    /// the largest difference of a channel, in sRGB from 0 to 1
+    pub max_difference: f32,
+    /// the actual image faded out, with the mismatched pixels in red
+    pub image: Image,
+}
+
+impl ImageDiff {
+    pub fn is_match(&self) -> bool {
+        self.mismatched.is_empty()
+    }
+}
+
+/// Compares two 2D images pixel by pixel, in sRGB.
+///
+/// A pixel matches when none of its channels differ by more than `tolerance`.
+pub fn compare_images(
+    actual: &Image,
+    expected: &Image,
+    tolerance: f32,
+) -> Result<ImageDiff, ImageError> {
+    let size = actual.size();
+    if size != expected.size() {
+        return Err(ImageError::Size {
+            actual: size,
+            expected: expected.size(),
+        });
+    }
+    let mut diff = ImageDiff {
+        mismatched: Vec::new(),
+        max_difference: 0.0,
+        image: actual.clone(),
+    };
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let a = actual.get_color_at(x, y).map_err(ImageError::Access)?;
+            let b = expected.get_color_at(x, y).map_err(ImageError::Access)?;
+            let difference = channels(a)
+                .into_iter()
+                .zip(channels(b))
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0, f32::max);
+            diff.max_difference = diff.max_difference.max(difference);
+            let highlighted = if difference > tolerance {
+                diff.mismatched.push(UVec2::new(x, y));
+                Color::srgb(1.0, 0.0, 0.0)
+            } else {
+                a.with_alpha(a.alpha() * 0.25)
+            };
+            diff.image
+                .set_color_at(x, y, highlighted)
+                .map_err(ImageError::Access)?;
+        }
+    }
+    Ok(diff)
+}
+
+fn channels(color: Color) -> [f32; 4] {
+    let Srgba {
+        red,
+        green,
+        blue,
+        alpha,
+    } = color.to_srgba();
+    [red, green, blue, alpha]
+}
+
+/// Encodes an image as PNG, e.g. for `assert_png_snapshot!`.
+pub fn image_to_png(image: &Image) -> Result<Vec<u8>, ImageError> {
+    let dynamic = image
+        .clone()
+        .try_into_dynamic()
+        .map_err(ImageError::Convert)?;
+    let mut png = Vec::new();
+    dynamic
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(ImageError::Encode)?;
+    Ok(png)
+}
+
+pub trait ImageAssertions {
+    fn matches_image(&mut self, expected: &Image, tolerance: f32);
+
+    /// Like [`matches_image`](Self::matches_image), but on failure the
+    /// [`ImageDiff`] is written next to the test's unnamed snapshot, as
+    /// `<snapshot name>.diff.png`; see [`assert_image_matches!`](crate::assert_image_matches).
+    #[cfg(feature = "insta")]
+    fn matches_image_of(&mut self, expected: &Image, tolerance: f32, test: SnapshotTest);
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl ImageAssertions for Spec<'_, Image> {
+    #[track_caller]
+    fn matches_image(&mut self, expected: &Image, tolerance: f32) {
+        match_image(self, expected, tolerance, |_| {
+            Err("it needs `assert_image_matches!`".into())
+        });
+    }
+
+    #[cfg(feature = "insta")]
+    #[track_caller]
+    fn matches_image_of(&mut self, expected: &Image, tolerance: f32, test: SnapshotTest) {
+        match_image(self, expected, tolerance, |image| test.write_diff(image));
+    }
+}
+
+#[track_caller]
+fn match_image(
+    spec: &mut Spec<'_, Image>,
+    expected: &Image,
+    tolerance: f32,
+    write_diff: impl FnOnce(&Image) -> Result<PathBuf, Box<dyn Error>>,
+) {
+    let diff = match compare_images(spec.subject, expected, tolerance) {
+        Ok(diff) if diff.is_match() => return,
+        Ok(diff) => diff,
+        Err(error) => {
+            AssertionFailure::from_spec(spec)
+                .with_expected(format!("image within <{tolerance}>"))
+                .with_actual(error.to_string())
+                .fail();
+            return;
+        }
+    };
+    let written = write_diff(&diff.image)
+        .map(|path| format!("see {}", path.display()))
+        .unwrap_or_else(|error| format!("the diff wasn't written: {error}"));
+    AssertionFailure::from_spec(spec)
+        .with_expected(format!("image within <{tolerance}>"))
+        .with_actual(format!(
+            "<{}> pixel(s) differing by up to <{}>, the first at <{}>\n\t{written}",
+            diff.mismatched.len(),
+            diff.max_difference,
+            diff.mismatched[0],
+        ))
+        .fail();
+}
+
+/// The test an assertion is in, which insta names its snapshots after.
+#[cfg(feature = "insta")]
+#[derive(Clone, Copy, Debug)]
+pub struct SnapshotTest {
+    pub module_path: &'static str,
+    /// the test function's name, without its path
+    pub function: &'static str,
+    /// the test's file, as `file!()` gives it
+    pub file: &'static str,
+}
+
+#[cfg(feature = "insta")]
+impl SnapshotTest {
+    /// `item_path` is the type name of an item declared in the test function,
+    /// which may be nested in closures.
+    pub fn new(module_path: &'static str, item_path: &'static str, file: &'static str) -> Self {
+        let mut path = item_path.rsplit_once("::").map_or("", |(path, _)| path);
+        while let Some(outer) = path.strip_suffix("::{{closure}}") {
+            path = outer;
+        }
+        Self {
+            module_path,
+            function: path.rsplit("::").next().unwrap_or(path),
+            file,
+        }
+    }
+
+    /// insta's name for the test's first unnamed snapshot, e.g.
+    /// `my_crate__tests__noise@seed_1`
+    pub fn snapshot_name(&self) -> String {
+        let settings = insta::Settings::clone_current();
+        let mut name = self
+            .function
+            .strip_prefix("test_")
+            .unwrap_or(self.function)
+            .to_string();
+        if let Some(suffix) = settings.snapshot_suffix() {
+            name = format!("{name}@{suffix}");
+        }
+        if settings.prepend_module_to_snapshot() {
+            name = format!("{}__{name}", self.module_path.replace("::", "__"));
+        }
+        name
+    }
+
+    /// the directory insta writes the test's snapshots to
+    pub fn snapshot_directory(&self) -> Result<PathBuf, Box<dyn Error>> {
+        Ok(test_directory(self.file)?.join(insta::Settings::clone_current().snapshot_path()))
+    }
+
+    fn write_diff(&self, image: &Image) -> Result<PathBuf, Box<dyn Error>> {
+        let directory = self.snapshot_directory()?;
+        fs::create_dir_all(&directory)?;
+        let path = directory.join(format!("{}.diff.png", self.snapshot_name()));
+        fs::write(&path, image_to_png(image)?)?;
+        Ok(path)
+    }
+}
+
+/// the directory of the test's file, which insta's snapshot path is relative to
+#[cfg(feature = "insta")]
+fn test_directory(file: &str) -> Result<PathBuf, Box<dyn Error>> {
+    // `file!()` is relative to the workspace, which contains the package
+    let manifest = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
+    let file = manifest
+        .ancestors()
+        .map(|directory| directory.join(file))
+        .find(|file| file.exists())
+        .ok_or_else(|| format!("can't find {file}"))?;
+    Ok(file.parent().unwrap_or(Path::new(".")).to_path_buf())
+}
+
+/// The [`SnapshotTest`] this is expanded in.
+#[cfg(feature = "insta")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! snapshot_test {
+    () => {{
+        fn item() {}
+        fn type_name_of<T>(_: T) -> &'static str {
+            ::std::any::type_name::<T>()
+        }
+        $crate::prelude::SnapshotTest::new(module_path!(), type_name_of(item), file!())
+    }};
+}
+
+/// Asserts that two [`Image`]s match within a tolerance, and on failure
+/// writes their [`ImageDiff`] next to the test's unnamed snapshot, as
+/// `<snapshot name>.diff.png`.
+///
+/// ```rust,ignore
+/// assert_image_matches!(generate_noise(seed), expected, 0.01);
+/// ```
+#[cfg(feature = "insta")]
+#[macro_export]
+macro_rules! assert_image_matches {
+    ($actual:expr, $expected:expr, $tolerance:expr) => {
+        $crate::prelude::ImageAssertions::matches_image_of(
+            &mut speculoos::assert_that(&$actual),
+            &$expected,
+            $tolerance,
+            $crate::snapshot_test!(),
+        )
+    };
+}
+
+/// Snapshots an [`Image`] as a PNG, with insta's binary snapshots.
+///
+/// ```rust,ignore
+/// assert_png_snapshot!(generate_noise(seed));
+/// assert_png_snapshot!("noise", generate_noise(seed));
+/// ```
+#[cfg(feature = "insta")]
+#[macro_export]
+macro_rules! assert_png_snapshot {
+    ($image:expr) => {
+        insta::assert_binary_snapshot!(".png", $crate::prelude::image_to_png(&$image).unwrap())
+    };
+    ($name:literal, $image:expr) => {
+        insta::assert_binary_snapshot!(
+            concat!($name, ".png"),
+            $crate::prelude::image_to_png(&$image).unwrap()
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::RenderAssetUsages,
+        render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    };
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn filled(width: u32, pixel: [u8; 4]) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &pixel,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[rstest]
+    fn test_compare_images() {
+        let mut actual = filled(2, [100, 100, 100, 255]);
+        actual
+            .set_color_at(1, 0, Color::srgb_u8(200, 100, 100))
+            .unwrap();
+
+        let diff = compare_images(&actual, &filled(2, [101, 100, 100, 255]), 0.01).unwrap();
+
+        assert_that!(diff.mismatched).is_equal_to(vec![UVec2::new(1, 0)]);
+        assert_that!(diff.max_difference).is_close_to(99.0 / 255.0, 1e-3);
+        assert_that!(diff.image.get_color_at(1, 0).unwrap())
+            .is_equal_to(Color::srgb(1.0, 0.0, 0.0));
+    }
+
+    #[rstest]
+    fn test_compare_images_size() {
+        let result = compare_images(&filled(2, [0; 4]), &filled(3, [0; 4]), 0.0);
+
+        assert_that!(result.err().map(|error| error.to_string()))
+            .is_equal_to(Some("the image is [2, 2], expected [3, 2]".to_string()));
+    }
+
+    #[rstest]
+    fn test_matches_image() {
+        assert_that!(filled(2, [10, 20, 30, 255]))
+            .matches_image(&filled(2, [11, 19, 30, 255]), 0.01);
+    }
+
+    #[cfg(feature = "insta")]
+    #[rstest]
+    fn test_diff_next_to_png_snapshot() {
+        let image = filled(2, [10, 20, 30, 255]);
+        assert_png_snapshot!(image);
+        let test = crate::snapshot_test!();
+        let snapshot = test
+            .snapshot_directory()
+            .unwrap()
+            .join(format!("{}.snap.png", test.snapshot_name()));
+        let diff = snapshot.with_extension("").with_extension("diff.png");
+
+        let result = std::panic::catch_unwind(|| {
+            assert_image_matches!(image, filled(2, [90, 20, 30, 255]), 0.01);
+        });
+        let written = diff.exists();
+        let _ = fs::remove_file(&diff);
+
+        assert_that!(result).is_err();
+        assert_that!(snapshot.exists())
+            .named("png snapshot")
+            .is_true();
+        assert_that!(written).named("diff").is_true();
+    }
+
+    #[cfg(feature = "insta")]
+    #[rstest]
+    fn test_snapshot_test_in_closure() {
+        let in_closure = || crate::snapshot_test!();
+
+        let test = in_closure();
+
+        assert_that!(test.function).is_equal_to("test_snapshot_test_in_closure");
+        assert_that!(test.module_path).ends_with("images::tests");
+    }
+
+    #[rstest]
+    fn test_image_to_png() {
+        let png = image_to_png(&filled(2, [10, 20, 30, 255])).unwrap();
+
+        assert_that!(png[1..4].to_vec()).is_equal_to(b"PNG".to_vec());
+    }
+}
@@ -18,15 +18,36 @@ mod deprecated {
     const_assert_eq!(MAJOR, 0);
 }
 
+pub(crate) mod as_world;
 #[cfg(feature = "speculoos")]
 pub(crate) mod assertions;
+#[cfg(feature = "trait_test_assets")]
+pub(crate) mod assets;
+pub(crate) mod executor;
 #[cfg(any(test, feature = "minimal"))]
 pub(crate) mod fixtures;
+#[cfg(feature = "image")]
+pub(crate) mod images;
+#[cfg(feature = "trait_test_assets")]
+pub(crate) mod loaders;
+#[cfg(feature = "logs")]
+pub(crate) mod logs;
+#[cfg(feature = "mesh")]
+pub(crate) mod meshes;
 #[cfg(any(all(test, feature = "rstest"), feature = "trait_collect_messages"))]
 pub(crate) mod messages;
+#[cfg(feature = "proptest")]
+pub(crate) mod properties;
+#[cfg(feature = "rstest")]
+pub(crate) mod render;
+#[cfg(feature = "scenario")]
+pub(crate) mod scenarios;
+#[cfg(feature = "trait_capture_system_errors")]
+pub(crate) mod system_errors;
 #[allow(unused_imports)] // Silence warning about name starting with `test_`
 pub(crate) mod test_app;
 pub(crate) mod traits;
+pub(crate) mod update_panic;
 
 #[cfg(feature = "insta")]
 #[macro_export]
@@ -41,15 +62,35 @@ macro_rules! set_snapshot_suffix {
 pub mod prelude {
     #[cfg(feature = "speculoos")]
     pub use super::assertions::*;
+    #[cfg(feature = "trait_test_assets")]
+    pub use super::assets::*;
     #[cfg(any(test, feature = "rstest"))]
     pub use super::fixtures::*;
+    #[cfg(feature = "image")]
+    pub use super::images::*;
+    #[cfg(feature = "trait_test_assets")]
+    pub use super::loaders::*;
+    #[cfg(feature = "logs")]
+    pub use super::logs::*;
+    #[cfg(feature = "mesh")]
+    pub use super::meshes::*;
     #[cfg(feature = "trait_collect_messages")]
     pub use super::messages::*;
+    #[cfg(feature = "proptest")]
+    pub use super::properties::*;
+    #[cfg(feature = "rstest")]
+    pub use super::render::*;
+    #[cfg(feature = "scenario")]
+    pub use super::scenarios::*;
     #[cfg(feature = "insta")]
     pub use super::set_snapshot_suffix;
-    pub use super::test_app::*;
+    #[cfg(feature = "trait_capture_system_errors")]
+    pub use super::system_errors::*;
     #[allow(unused_imports)]
     pub use super::traits::*;
+    pub use super::{as_world::*, executor::*, test_app::*, update_panic::UpdatePanic};
+    #[cfg(all(feature = "image", feature = "insta"))]
+    pub use super::{assert_image_matches, assert_png_snapshot};
 }
 
 #[doc = include_str!("../Readme.md")]
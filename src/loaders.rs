@@ -0,0 +1,376 @@
+use std::{
+    convert::Infallible,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    sync::{Arc, Mutex},
+};
+
+use bevy_app::Plugins;
+use bevy_asset::{
+    Asset, AssetApp, AssetLoader, AssetPath, AssetServer, ErasedLoadedAsset, LoadContext,
+    LoadedAsset, UntypedAssetId, VisitAssetDependencies,
+    io::{Reader, memory::Value},
+};
+use bevy_internal::reflect::TypePath;
+
+use crate::{
+    assets::AssetWaitError,
+    fixtures::{asset_test_app, minimal_test_app},
+    test_app::TestApp,
+    traits::TestAssets,
+};
+
+/// Runs an [`AssetLoader`] on some bytes, in an app of its own where it's
+/// the only loader.
+///
+/// ```rust,ignore
+/// let output = LoaderHarness::new(LevelLoader)
+///     .with_settings(LevelSettings { strict: true })
+///     // register the types of labeled assets
+///     .with_plugins(|app: &mut App| {
+///         app.init_asset::<Tile>();
+///     })
+///     .run("levels/1.ron", b"(size: 3)")
+///     .unwrap();
+/// ```
+pub struct LoaderHarness<L: AssetLoader> {
+    loader: L,
+    settings: L::Settings,
+    max_frames: u32,
+    app: TestApp,
+}
+
+impl<L: AssetLoader> LoaderHarness<L>
+where
+    L::Error: Send,
+{
+    pub fn new(loader: L) -> Self {
+        let mut app = asset_test_app((), minimal_test_app(()));
+        app.init_asset::<L::Asset>().init_asset::<CapturedAsset>();
+        Self {
+            loader,
+            settings: L::Settings::default(),
+            max_frames: 1000,
+            app,
+        }
+    }
+
+    pub fn with_settings(mut self, settings: L::Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// how many frames [`LoaderHarness::run`] waits for the loader, 1000 by
+    /// default
+    pub fn with_max_frames(mut self, max_frames: u32) -> Self {
+        self.max_frames = max_frames;
+        self
+    }
+
+    pub fn with_plugins<P>(mut self, plugins: impl Plugins<P>) -> Self {
+        self.app.add_plugins(plugins);
+        self
+    }
+
+    /// adds another file, e.g. one read with [`LoadContext::read_asset_bytes`]
+    pub fn with_test_asset(mut self, path: &str, bytes: impl Into<Value>) -> Self {
+        self.app.add_test_asset(path, bytes);
+        self
+    }
+
+    /// Loads `bytes` as if they were read from `path`.
+    pub fn run(
+        mut self,
+        path: impl Into<AssetPath<'static>>,
+        bytes: impl Into<Value>,
+    ) -> Result<LoaderOutput<L::Asset>, LoaderHarnessError<L::Error>> {
+        let path = path.into();
+        let result = Arc::new(Mutex::new(None));
+        self.app
+            .add_test_asset(path.path(), bytes)
+            .register_asset_loader(CapturingLoader {
+                loader: self.loader,
+                settings: Mutex::new(Some(self.settings)),
+                result: result.clone(),
+            });
+
+        self.app
+            .load_and_wait::<CapturedAsset>(path, self.max_frames)
+            .map_err(LoaderHarnessError::Wait)?;
+        let result = result
+            .lock()
+            .unwrap()
+            .take()
+            .expect("the placeholder is only loaded by the capturing loader");
+
+        let server = self.app.world().resource::<AssetServer>();
+        result.map_err(LoaderHarnessError::Loader).map(|loaded| {
+            let mut ids = Vec::new();
+            loaded.get().visit_dependencies(&mut |id| ids.push(id));
+            LoaderOutput {
+                dependencies: ids
+                    .into_iter()
+                    .filter_map(|id: UntypedAssetId| server.get_path(id))
+                    .map(AssetPath::into_owned)
+                    .collect(),
+                loaded,
+            }
+        })
+    }
+}
+
+/// What an [`AssetLoader`] produced, see [`LoaderHarness`].
+pub struct LoaderOutput<A: Asset> {
+    loaded: LoadedAsset<A>,
+    dependencies: Vec<AssetPath<'static>>,
+}
+
+impl<A: Asset> LoaderOutput<A> {
+    pub fn asset(&self) -> &A {
+        self.loaded.get()
+    }
+
+    pub fn take(self) -> A {
+        self.loaded.take()
+    }
+
+    /// the labels of the sub-assets, sorted
+    pub fn labels(&self) -> Vec<&str> {
+        let mut labels = self.loaded.iter_labels().collect::<Vec<_>>();
+        labels.sort_unstable();
+        labels
+    }
+
+    pub fn labeled<B: Asset>(&self, label: &'static str) -> Option<&B> {
+        self.loaded
+            .get_labeled(label)
+            .and_then(ErasedLoadedAsset::get::<B>)
+    }
+
+    /// the paths of the assets the loaded asset holds handles to
+    pub fn dependencies(&self) -> &[AssetPath<'static>] {
+        &self.dependencies
+    }
+}
+
+/// Why [`LoaderHarness::run`] has no output.
+#[derive(Debug)]
+pub enum LoaderHarnessError<E> {
+    /// the loader returned an error
+    Loader(E),
+    /// the loader didn't run, or didn't finish in time
+    Wait(AssetWaitError),
+}
+
+impl<E: Display> Display for LoaderHarnessError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Loader(error) => write!(f, "{error}"),
+            Self::Wait(error) => write!(f, "the loader didn't finish, {error}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for LoaderHarnessError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Loader(error) => Some(error),
+            Self::Wait(error) => Some(error),
+        }
+    }
+}
+
+/// what the server gets instead of the wrapped loader's asset, which is
+/// captured for [`LoaderOutput`]
+#[derive(Asset)]
+struct CapturedAsset;
+
+impl TypePath for CapturedAsset {
+    fn type_path() -> &'static str {
+        concat!(module_path!(), "::CapturedAsset")
+    }
+
+    fn short_type_path() -> &'static str {
+        "CapturedAsset"
+    }
+}
+
+type CapturedResult<L> = Result<LoadedAsset<<L as AssetLoader>::Asset>, <L as AssetLoader>::Error>;
+
+struct CapturingLoader<L: AssetLoader> {
+    loader: L,
+    settings: Mutex<Option<L::Settings>>,
+    result: Arc<Mutex<Option<CapturedResult<L>>>>,
+}
+
+impl<L: AssetLoader> TypePath for CapturingLoader<L> {
+    fn type_path() -> &'static str {
+        L::type_path()
+    }
+
+    fn short_type_path() -> &'static str {
+        L::short_type_path()
+    }
+}
+
+impl<L: AssetLoader> AssetLoader for CapturingLoader<L>
+where
+    L::Error: Send,
+{
+    type Asset = CapturedAsset;
+    type Settings = ();
+    type Error = Infallible;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<CapturedAsset, Infallible> {
+        let settings = self.settings.lock().unwrap().take().unwrap_or_default();
+        // collects labeled assets and dependencies like the server would
+        let mut context = load_context.begin_labeled_asset();
+        let result = self
+            .loader
+            .load(reader, &settings, &mut context)
+            .await
+            .map(|asset| context.finish(asset));
+        *self.result.lock().unwrap() = Some(result);
+        Ok(CapturedAsset)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.loader.extensions()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::reflect::TypePath;
+    use bevy_asset::Handle;
+    use bevy_ecs::error::BevyError;
+    use rstest::rstest;
+    use serde::{Deserialize, Serialize};
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[derive(Asset, TypePath, Debug)]
+    struct Level {
+        #[dependency]
+        tileset: Handle<Tileset>,
+    }
+
+    #[derive(Asset, TypePath, Debug, PartialEq)]
+    struct Row(String);
+
+    #[derive(Asset, TypePath, Debug)]
+    struct Tileset;
+
+    #[derive(Default, Serialize, Deserialize)]
+    struct LevelSettings {
+        max_rows: usize,
+    }
+
+    /// one labeled `Row` per line, the tileset is `tiles.png` next to it
+    #[derive(TypePath)]
+    struct LevelLoader;
+
+    impl AssetLoader for LevelLoader {
+        type Asset = Level;
+        type Settings = LevelSettings;
+        type Error = BevyError;
+
+        async fn load(
+            &self,
+            reader: &mut dyn Reader,
+            settings: &LevelSettings,
+            load_context: &mut LoadContext<'_>,
+        ) -> Result<Level, BevyError> {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let text = String::from_utf8(bytes)?;
+            let rows = text.lines().collect::<Vec<_>>();
+            if rows.len() > settings.max_rows {
+                return Err(format!("{} rows, at most {}", rows.len(), settings.max_rows).into());
+            }
+            for (index, row) in rows.into_iter().enumerate() {
+                load_context.add_labeled_asset(format!("row{index}"), Row(row.to_string()));
+            }
+            let tileset = load_context.path().resolve_embed_str("tiles.png")?;
+            Ok(Level {
+                tileset: load_context.load(tileset),
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["level"]
+        }
+    }
+
+    fn level_harness() -> LoaderHarness<LevelLoader> {
+        LoaderHarness::new(LevelLoader)
+            .with_settings(LevelSettings { max_rows: 2 })
+            .with_plugins(|app: &mut bevy_app::App| {
+                app.init_asset::<Row>().init_asset::<Tileset>();
+            })
+    }
+
+    #[rstest]
+    fn test_loader_harness() {
+        let output = level_harness().run("levels/1.level", b"#.#\n...").unwrap();
+
+        assert_that!(output.labels()).is_equal_to(vec!["row0", "row1"]);
+        assert_that!(output.labeled::<Row>("row1")).is_equal_to(Some(&Row("...".to_string())));
+        assert_that!(output.labeled::<Tileset>("row1")).is_none();
+        assert_that!(output.dependencies().to_vec())
+            .is_equal_to(vec![AssetPath::from("levels/tiles.png")]);
+    }
+
+    #[rstest]
+    fn test_loader_harness_error() {
+        let error = level_harness()
+            .run("levels/1.level", b"#\n#\n#")
+            .err()
+            .unwrap();
+
+        assert_that!(error.to_string()).starts_with("3 rows, at most 2");
+    }
+
+    /// never finishes
+    #[derive(TypePath)]
+    struct PendingLoader;
+
+    impl AssetLoader for PendingLoader {
+        type Asset = Tileset;
+        type Settings = ();
+        type Error = BevyError;
+
+        async fn load(
+            &self,
+            _reader: &mut dyn Reader,
+            _settings: &(),
+            _load_context: &mut LoadContext<'_>,
+        ) -> Result<Tileset, BevyError> {
+            std::future::pending().await
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["png"]
+        }
+    }
+
+    #[rstest]
+    fn test_loader_harness_still_loading() {
+        let error = LoaderHarness::new(PendingLoader)
+            .with_max_frames(3)
+            .run("tiles.png", b"")
+            .err()
+            .unwrap();
+
+        assert_that!(matches!(error, LoaderHarnessError::Wait(_))).is_true();
+        assert_that!(error.to_string()).is_equal_to(
+            "the loader didn't finish, still loading after 3 frame(s): `tiles.png`".to_string(),
+        );
+    }
+}
@@ -0,0 +1,408 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::{self as std_fmt, Debug, Display, Formatter},
+    sync::{Arc, Mutex, Once},
+};
+
+use bevy_app::{App, First, Last, Plugin};
+use bevy_ecs::{
+    resource::Resource,
+    schedule::Schedules,
+    system::{Res, ResMut},
+    world::World,
+};
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::{
+    Layer,
+    filter::LevelFilter,
+    fmt,
+    layer::{Context, SubscriberExt},
+    registry::Registry,
+};
+
+use crate::executor::run_single_threaded;
+
+/// A single log event, recorded while a [`LogCapturePlugin`] was active.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedLog {
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+    pub fields: Vec<(String, String)>,
+}
+
+impl Display for CapturedLog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std_fmt::Result {
+        write!(f, "{} {}: {}", self.level, self.target, self.message)?;
+        for (name, value) in &self.fields {
+            write!(f, " {name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Every log event captured for the app, shared with the tracing layer.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct CapturedLogs(Arc<Mutex<Vec<CapturedLog>>>);
+
+impl CapturedLogs {
+    pub fn get(&self) -> Vec<CapturedLog> {
+        self.0.lock().unwrap().clone()
+    }
+
+    fn push(&self, log: CapturedLog) {
+        self.0.lock().unwrap().push(log);
+    }
+}
+
+thread_local! {
+    static CURRENT_LOGS: RefCell<Option<CapturedLogs>> = const { RefCell::new(None) };
+    static DISPATCHER_INSTALLED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Routes log events on the test thread into [`CapturedLogs`], and prints them
+/// (`INFO` and above) through the test harness so parallel tests don't mix.
+///
+/// `log` records (used throughout `bevy_ecs`) are forwarded as well.
+///
+/// Limits:
+/// - every schedule runs single-threaded, see [`SingleThreadedExecutorPlugin`],
+///   tasks on bevy's task pools are still not captured
+/// - the capturing subscriber stays the default of the test thread until it
+///   ends, later apps on the same thread share it; a dropped [`TestApp`] stops
+///   capturing, a dropped [`App`] doesn't
+///
+/// [`SingleThreadedExecutorPlugin`]: crate::executor::SingleThreadedExecutorPlugin
+/// [`TestApp`]: crate::test_app::TestApp
+#[derive(Debug, Default)]
+pub struct LogCapturePlugin;
+
+impl Plugin for LogCapturePlugin {
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn build(&self, app: &mut App) {
+        capture_logs_in(app.world_mut());
+    }
+}
+
+pub(crate) fn capture_logs_in(world: &mut World) {
+    if world.contains_resource::<CapturedLogs>() {
+        return;
+    }
+    install_capture_dispatcher();
+
+    let logs = CapturedLogs::default();
+    CURRENT_LOGS.set(Some(logs.clone()));
+    world.insert_resource(logs);
+    world
+        .get_resource_or_init::<Schedules>()
+        .add_systems(First, |logs: Res<CapturedLogs>| {
+            // several apps might share a thread, only one updates at a time
+            CURRENT_LOGS.set(Some(logs.clone()));
+        });
+    run_single_threaded(world);
+}
+
+/// Stops capturing into `logs` on this thread, so logs after the app is gone
+/// don't end up in it.
+pub(crate) fn release_logs(logs: &CapturedLogs) {
+    CURRENT_LOGS.with_borrow_mut(|current| {
+        if current
+            .as_ref()
+            .is_some_and(|current| Arc::ptr_eq(&current.0, &logs.0))
+        {
+            *current = None;
+        }
+    });
+}
+
+fn install_capture_dispatcher() {
+    static LOG_TRACER: Once = Once::new();
+    LOG_TRACER.call_once(|| {
+        // fails if a logger was set already, e.g. by bevy's `LogPlugin`
+        let _ = tracing_log::LogTracer::init();
+    });
+    if !DISPATCHER_INSTALLED.replace(true) {
+        let subscriber = Registry::default().with(CaptureLayer).with(
+            fmt::layer()
+                .with_test_writer()
+                .with_filter(LevelFilter::INFO),
+        );
+        // keep it as default for the rest of this thread's lifetime
+        std::mem::forget(tracing::subscriber::set_default(subscriber));
+    }
+}
+
+struct CaptureLayer;
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let Some(logs) = CURRENT_LOGS.with_borrow(Clone::clone) else {
+            return;
+        };
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+        let mut visitor = CaptureVisitor::default();
+        event.record(&mut visitor);
+        logs.push(CapturedLog {
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        });
+    }
+}
+
+#[derive(Default)]
+struct CaptureVisitor {
+    message: String,
+    fields: Vec<(String, String)>,
+}
+
+impl CaptureVisitor {
+    fn record(&mut self, field: &Field, value: String) {
+        match field.name() {
+            "message" => self.message = value,
+            // metadata of `log` records, already normalized
+            name if name.starts_with("log.") => {}
+            name => self.fields.push((name.to_string(), value)),
+        }
+    }
+}
+
+impl Visit for CaptureVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.record(field, format!("{value:?}"));
+    }
+}
+
+/// Fails the test at the end of an update in which a log of `level` (or more
+/// severe) was emitted outside of `allowed_targets`.
+///
+/// The check runs in `Last`, so logs of systems ordered after it are only
+/// caught by the next update, or when the [`TestApp`] is dropped, which checks
+/// once more. Captures logs like the [`LogCapturePlugin`], with its limits.
+///
+/// Pass it to a fixture to enable it:
+/// `#[from(minimal_test_app)] #[with(PanicOnErrorLogPlugin::default())]`
+///
+/// [`TestApp`]: crate::test_app::TestApp
+#[derive(Clone, Debug)]
+pub struct PanicOnErrorLogPlugin {
+    pub level: Level,
+    pub allowed_targets: Vec<String>,
+}
+
+impl Default for PanicOnErrorLogPlugin {
+    fn default() -> Self {
+        Self {
+            level: Level::WARN,
+            allowed_targets: Vec::new(),
+        }
+    }
+}
+
+impl PanicOnErrorLogPlugin {
+    /// only panic on `error!`
+    pub fn errors_only() -> Self {
+        Self {
+            level: Level::ERROR,
+            ..Self::default()
+        }
+    }
+
+    /// ignore logs from `target` and its submodules
+    pub fn allow_target(mut self, target: impl Into<String>) -> Self {
+        self.allowed_targets.push(target.into());
+        self
+    }
+
+    pub fn is_allowed(&self, log: &CapturedLog) -> bool {
+        // more verbose levels compare as greater
+        log.level > self.level
+            || self.allowed_targets.iter().any(|allowed| {
+                log.target == *allowed
+                    || log
+                        .target
+                        .strip_prefix(allowed.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+    }
+}
+
+impl Plugin for PanicOnErrorLogPlugin {
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn build(&self, app: &mut App) {
+        capture_logs_in(app.world_mut());
+        app.insert_resource(ErrorLogCheck {
+            plugin: self.clone(),
+            checked: 0,
+        })
+        .add_systems(
+            Last,
+            |mut check: ResMut<ErrorLogCheck>, logs: Res<CapturedLogs>| {
+                let unexpected = check.unexpected(&logs);
+                if !unexpected.is_empty() {
+                    panic!("{}", check.message(&unexpected));
+                }
+            },
+        );
+    }
+}
+
+/// the logs [`PanicOnErrorLogPlugin`] went through already
+#[derive(Debug, Resource)]
+pub(crate) struct ErrorLogCheck {
+    plugin: PanicOnErrorLogPlugin,
+    checked: usize,
+}
+
+impl ErrorLogCheck {
+    /// the logs since the last check that aren't allowed
+    pub(crate) fn unexpected(&mut self, logs: &CapturedLogs) -> Vec<CapturedLog> {
+        let logs = logs.get();
+        let unexpected = logs[self.checked..]
+            .iter()
+            .filter(|log| !self.plugin.is_allowed(log))
+            .cloned()
+            .collect();
+        self.checked = logs.len();
+        unexpected
+    }
+
+    pub(crate) fn message(&self, unexpected: &[CapturedLog]) -> String {
+        format!(
+            "{} unexpected log(s) at level {} or above:\n{}",
+            unexpected.len(),
+            self.plugin.level,
+            unexpected
+                .iter()
+                .map(|log| format!("  {log}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::Update;
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    fn log(level: Level, target: &str) -> CapturedLog {
+        CapturedLog {
+            level,
+            target: target.into(),
+            message: "message".into(),
+            fields: Vec::new(),
+        }
+    }
+
+    #[rstest]
+    #[case(Level::ERROR, "my_game", false)]
+    #[case(Level::WARN, "my_game", false)]
+    #[case(Level::INFO, "my_game", true)]
+    #[case(Level::WARN, "noisy", true)]
+    #[case(Level::WARN, "noisy::module", true)]
+    #[case(Level::WARN, "noisy_neighbour", false)]
+    fn test_panic_on_error_log_is_allowed(
+        #[case] level: Level,
+        #[case] target: &str,
+        #[case] allowed: bool,
+    ) {
+        let plugin = PanicOnErrorLogPlugin::default().allow_target("noisy");
+        assert_that!(plugin.is_allowed(&log(level, target))).is_equal_to(allowed);
+    }
+
+    #[rstest]
+    fn test_log_capture_plugin_records_logs(
+        #[from(minimal_test_app)]
+        #[with(LogCapturePlugin)]
+        mut app: TestApp,
+    ) {
+        app.add_systems(Update, || tracing::info!(answer = 42, "hello"));
+
+        app.update();
+
+        let logs = app.world().resource::<CapturedLogs>().get();
+        let log = logs.iter().find(|log| log.message == "hello");
+        assert_that!(log).is_some().matches(|log| {
+            log.level == Level::INFO && log.fields == vec![("answer".to_string(), "42".to_string())]
+        });
+    }
+
+    #[rstest]
+    fn test_log_capture_plugin_stops_when_app_dropped() {
+        let first = minimal_test_app(LogCapturePlugin);
+        let first_logs = first.world().resource::<CapturedLogs>().clone();
+        drop(first);
+
+        tracing::info!("between the apps");
+        let mut second = minimal_test_app(());
+        second.add_systems(Update, || tracing::info!("second app"));
+        second.update();
+
+        let messages: Vec<_> = first_logs
+            .get()
+            .into_iter()
+            .map(|log| log.message)
+            .collect();
+        assert_that!(messages).does_not_contain("between the apps".to_string());
+        assert_that!(messages).does_not_contain("second app".to_string());
+    }
+
+    #[rstest]
+    #[should_panic(expected = "unexpected log(s)")]
+    fn test_panic_on_error_log_panics(
+        #[from(minimal_test_app)]
+        #[with(PanicOnErrorLogPlugin::default())]
+        mut app: TestApp,
+    ) {
+        app.add_systems(Update, || tracing::warn!("save failed"));
+
+        app.update();
+    }
+
+    #[rstest]
+    #[should_panic(expected = "1 unexpected log(s)")]
+    fn test_panic_on_error_log_checks_on_drop(
+        #[from(minimal_test_app)]
+        #[with(PanicOnErrorLogPlugin::default())]
+        mut app: TestApp,
+    ) {
+        #[derive(Resource)]
+        struct WarnOnDrop;
+
+        impl Drop for WarnOnDrop {
+            fn drop(&mut self) {
+                tracing::warn!("dropped");
+            }
+        }
+
+        app.insert_resource(WarnOnDrop);
+
+        app.update();
+    }
+
+    #[rstest]
+    fn test_panic_on_error_log_allow_list(
+        #[from(minimal_test_app)]
+        #[with(PanicOnErrorLogPlugin::default().allow_target("noisy"))]
+        mut app: TestApp,
+    ) {
+        app.add_systems(Update, || tracing::error!(target: "noisy", "ignore me"));
+
+        app.update();
+    }
+}
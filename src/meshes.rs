@@ -0,0 +1,320 @@
+use std::fmt::Write;
+
+use bevy_internal::math::Vec3;
+use bevy_mesh::{Mesh, MeshVertexAttribute, PrimitiveTopology, VertexAttributeValues};
+use speculoos::{AssertionFailure, Spec};
+
+/// the most problems listed in a failure message
+const MAX_LISTED: usize = 8;
+
+/// Assertions for generated meshes, e.g.
+/// `assert_that!(mesh).has_valid_indices()`
+pub trait MeshAssertions {
+    fn has_vertex_count(&mut self, expected: usize);
+    fn has_attribute(&mut self, attribute: MeshVertexAttribute);
+    /// no index is out of range of the vertices
+    fn has_valid_indices(&mut self);
+    fn has_normalized_normals(&mut self);
+    /// the bounding box of the positions
+    fn has_bounds(&mut self, min: Vec3, max: Vec3, epsilon: f32);
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+impl MeshAssertions for Spec<'_, Mesh> {
+    fn has_vertex_count(&mut self, expected: usize) {
+        let count = self.subject.count_vertices();
+        if count != expected {
+            AssertionFailure::from_spec(self)
+                .with_expected(format!("mesh with <{expected}> vertices"))
+                .with_actual(format!("<{count}>"))
+                .fail();
+        }
+    }
+
+    fn has_attribute(&mut self, attribute: MeshVertexAttribute) {
+        if !self.subject.contains_attribute(attribute.id) {
+            let names = self
+                .subject
+                .attributes()
+                .map(|(attribute, _)| attribute.name)
+                .collect::<Vec<_>>();
+            AssertionFailure::from_spec(self)
+                .with_expected(format!("mesh with <{}>", attribute.name))
+                .with_actual(format!("only <{names:?}>"))
+                .fail();
+        }
+    }
+
+    fn has_valid_indices(&mut self) {
+        let count = self.subject.count_vertices();
+        let Some(indices) = self.subject.indices() else {
+            return;
+        };
+        let invalid = indices
+            .iter()
+            .enumerate()
+            .filter(|(_, index)| *index >= count)
+            .map(|(position, index)| format!("indices[{position}] = {index}"))
+            .collect::<Vec<_>>();
+        if !invalid.is_empty() {
+            AssertionFailure::from_spec(self)
+                .with_expected(format!("indices below the vertex count <{count}>"))
+                .with_actual(listed(invalid))
+                .fail();
+        }
+    }
+
+    fn has_normalized_normals(&mut self) {
+        let Some(normals) = self
+            .subject
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3)
+        else {
+            AssertionFailure::from_spec(self)
+                .with_expected("mesh with normalized normals".to_string())
+                .with_actual("no normals".to_string())
+                .fail();
+            return;
+        };
+        let invalid = normals
+            .iter()
+            .map(|normal| Vec3::from(*normal))
+            .enumerate()
+            .filter(|(_, normal)| !normal.is_normalized())
+            .map(|(vertex, normal)| format!("vertex {vertex}: length {}", normal.length()))
+            .collect::<Vec<_>>();
+        if !invalid.is_empty() {
+            AssertionFailure::from_spec(self)
+                .with_expected("mesh with normalized normals".to_string())
+                .with_actual(listed(invalid))
+                .fail();
+        }
+    }
+
+    fn has_bounds(&mut self, min: Vec3, max: Vec3, epsilon: f32) {
+        let bounds = self
+            .subject
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(|positions| {
+                let positions = positions.as_float3()?;
+                positions
+                    .iter()
+                    .map(|position| Vec3::from(*position))
+                    .map(|position| (position, position))
+                    .reduce(|(min, max), (position, _)| (min.min(position), max.max(position)))
+            });
+        let is_close = |(actual_min, actual_max): (Vec3, Vec3)| {
+            actual_min.abs_diff_eq(min, epsilon) && actual_max.abs_diff_eq(max, epsilon)
+        };
+        if !bounds.is_some_and(is_close) {
+            AssertionFailure::from_spec(self)
+                .with_expected(format!("bounds from <{min}> to <{max}> within <{epsilon}>"))
+                .with_actual(match bounds {
+                    Some((min, max)) => format!("from <{min}> to <{max}>"),
+                    None => "no positions".to_string(),
+                })
+                .fail();
+        }
+    }
+}
+
+fn listed(problems: Vec<String>) -> String {
+    let mut listed = problems
+        .iter()
+        .take(MAX_LISTED)
+        .cloned()
+        .collect::<Vec<_>>();
+    if problems.len() > MAX_LISTED {
+        listed.push(format!("and {} more", problems.len() - MAX_LISTED));
+    }
+    listed.join("\n\t")
+}
+
+/// A text form of a mesh that doesn't change between platforms, for
+/// snapshots, e.g. `insta::assert_snapshot!(mesh_snapshot(&mesh))`.
+///
+/// Floats are rounded to 4 decimals.
+pub fn mesh_snapshot(mesh: &Mesh) -> String {
+    let mut snapshot = String::new();
+    let topology = mesh.primitive_topology();
+    writeln!(snapshot, "topology: {topology:?}").unwrap();
+    writeln!(snapshot, "vertices: {}", mesh.count_vertices()).unwrap();
+    for (attribute, values) in mesh.attributes() {
+        writeln!(snapshot, "{} ({:?}):", attribute.name, attribute.format).unwrap();
+        for (vertex, row) in rows(values).into_iter().enumerate() {
+            writeln!(snapshot, "  {vertex}: {row}").unwrap();
+        }
+    }
+    match mesh.indices() {
+        Some(indices) => {
+            writeln!(snapshot, "indices: {}", indices.len()).unwrap();
+            let indices = indices
+                .iter()
+                .map(|index| index.to_string())
+                .collect::<Vec<_>>();
+            let per_row = match topology {
+                PrimitiveTopology::TriangleList => 3,
+                PrimitiveTopology::LineList => 2,
+                _ => indices.len().max(1),
+            };
+            for primitive in indices.chunks(per_row) {
+                writeln!(snapshot, "  {}", primitive.join(" ")).unwrap();
+            }
+        }
+        None => writeln!(snapshot, "indices: none").unwrap(),
+    }
+    snapshot
+}
+
+fn rounded(value: f64) -> String {
+    let rounded = format!("{value:.4}");
+    // `-0.0000` and `0.0000` are the same
+    if rounded
+        .trim_start_matches('-')
+        .trim_matches(['0', '.'])
+        .is_empty()
+    {
+        rounded.trim_start_matches('-').to_string()
+    } else {
+        rounded
+    }
+}
+
+fn float_row<F: Copy>(values: &[F]) -> String
+where
+    f64: From<F>,
+{
+    let values = values
+        .iter()
+        .map(|value| rounded(f64::from(*value)))
+        .collect::<Vec<_>>();
+    format!("[{}]", values.join(", "))
+}
+
+macro_rules! attribute_rows {
+    (
+        $values:expr,
+        scalars: [$($scalar:ident),*],
+        floats: [$($float:ident),*],
+        others: [$($other:ident),*]
+    ) => {
+        match $values {
+            $(VertexAttributeValues::$scalar(values) => {
+                values.iter().map(|value| rounded(f64::from(*value))).collect()
+            })*
+            $(VertexAttributeValues::$float(values) => {
+                values.iter().map(|value| float_row(value)).collect()
+            })*
+            $(VertexAttributeValues::$other(values) => {
+                values.iter().map(|value| format!("{value:?}")).collect()
+            })*
+        }
+    };
+}
+
+fn rows(values: &VertexAttributeValues) -> Vec<String> {
+    attribute_rows!(
+        values,
+        scalars: [Float16, Float32, Float64],
+        floats: [
+            Float16x2, Float16x4, Float32x2, Float32x3, Float32x4, Float64x2, Float64x3,
+            Float64x4
+        ],
+        others: [
+            Uint8, Uint8x2, Uint8x4, Sint8, Sint8x2, Sint8x4, Unorm8, Unorm8x2, Unorm8x4,
+            Snorm8, Snorm8x2, Snorm8x4, Uint16, Uint16x2, Uint16x4, Sint16, Sint16x2, Sint16x4,
+            Unorm16, Unorm16x2, Unorm16x4, Snorm16, Snorm16x2, Snorm16x4, Uint32, Uint32x2,
+            Uint32x3, Uint32x4, Sint32, Sint32x2, Sint32x3, Sint32x4, Unorm10_10_10_2, Unorm8x4Bgra
+        ]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::RenderAssetUsages;
+    use bevy_internal::math::primitives::Cuboid;
+    use bevy_mesh::Indices;
+    use rstest::{fixture, rstest};
+    use speculoos::assert_that;
+
+    use super::*;
+
+    /// a right triangle in the XY plane, facing +Z
+    #[fixture]
+    fn triangle() -> Mesh {
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 2.0, 0.0]],
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 3])
+        .with_inserted_indices(Indices::U16(vec![0, 1, 2]))
+    }
+
+    #[rstest]
+    fn test_mesh_assertions(triangle: Mesh) {
+        assert_that!(triangle).has_vertex_count(3);
+        assert_that!(triangle).has_attribute(Mesh::ATTRIBUTE_NORMAL);
+        assert_that!(triangle).has_valid_indices();
+        assert_that!(triangle).has_normalized_normals();
+        assert_that!(triangle).has_bounds(Vec3::ZERO, Vec3::new(1.0, 2.0, 0.0), 1e-6);
+
+        let cuboid = Mesh::from(Cuboid::default());
+        assert_that!(cuboid).has_normalized_normals();
+        assert_that!(cuboid).has_bounds(Vec3::splat(-0.5), Vec3::splat(0.5), 1e-6);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "indices[2] = 3")]
+    fn test_mesh_assertions_invalid_indices(triangle: Mesh) {
+        let triangle = triangle.with_inserted_indices(Indices::U32(vec![0, 1, 3]));
+        assert_that!(triangle).has_valid_indices();
+    }
+
+    #[rstest]
+    #[should_panic(expected = "vertex 1: length 2")]
+    fn test_mesh_assertions_unnormalized_normals(triangle: Mesh) {
+        let triangle = triangle.with_inserted_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            vec![[0.0, 0.0, 1.0], [0.0, 0.0, 2.0], [0.0, 0.0, 1.0]],
+        );
+        assert_that!(triangle).has_normalized_normals();
+    }
+
+    #[rstest]
+    #[should_panic(expected = "only <[\"Vertex_Position\", \"Vertex_Normal\"]>")]
+    fn test_mesh_assertions_missing_attribute(triangle: Mesh) {
+        assert_that!(triangle).has_attribute(Mesh::ATTRIBUTE_UV_0);
+    }
+
+    #[rstest]
+    fn test_mesh_snapshot(triangle: Mesh) {
+        let triangle = triangle.with_inserted_attribute(
+            Mesh::ATTRIBUTE_UV_0,
+            vec![[0.0, 0.0], [1.0 / 3.0, -0.0], [0.0, 1.0]],
+        );
+
+        assert_that!(mesh_snapshot(&triangle).as_str()).is_equal_to(
+            "topology: TriangleList
+vertices: 3
+Vertex_Position (Float32x3):
+  0: [0.0000, 0.0000, 0.0000]
+  1: [1.0000, 0.0000, 0.0000]
+  2: [0.0000, 2.0000, 0.0000]
+Vertex_Normal (Float32x3):
+  0: [0.0000, 0.0000, 1.0000]
+  1: [0.0000, 0.0000, 1.0000]
+  2: [0.0000, 0.0000, 1.0000]
+Vertex_Uv (Float32x2):
+  0: [0.0000, 0.0000]
+  1: [0.3333, 0.0000]
+  2: [0.0000, 1.0000]
+indices: 3
+  0 1 2
+",
+        );
+    }
+}
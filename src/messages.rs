@@ -1,11 +1,13 @@
-use std::marker::PhantomData;
+use std::{any::type_name, marker::PhantomData};
 
 use bevy_app::{App, Plugin, PostUpdate};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
-    message::{Message, MessageReader},
+    message::{Message, MessageReader, MessageRegistry, Messages},
     resource::Resource,
+    schedule::Schedules,
     system::ResMut,
+    world::World,
 };
 
 #[derive(Debug, Deref, DerefMut, Resource)]
@@ -23,6 +25,33 @@ impl<E: Message> Default for CollectedMessages<E> {
     }
 }
 
+/// Collects the messages passing `filter` into [`CollectedMessages`] during
+/// `PostUpdate`, without needing an `App` to add plugins to.
+///
+/// Nothing is collected in a world that never runs `PostUpdate`, such as the
+/// render world. Panics if `E` is collected already, a second collector would
+/// duplicate every message.
+pub(crate) fn collect_messages_in<E: Message + Clone>(
+    world: &mut World,
+    filter: impl Fn(&E) -> bool + Send + Sync + 'static,
+) {
+    assert!(
+        !world.contains_resource::<CollectedMessages<E>>(),
+        "`{}` messages are collected already",
+        type_name::<E>()
+    );
+    if !world.contains_resource::<Messages<E>>() {
+        MessageRegistry::register_message::<E>(world);
+    }
+    world.init_resource::<CollectedMessages<E>>();
+    world.get_resource_or_init::<Schedules>().add_systems(
+        PostUpdate,
+        move |mut messages: MessageReader<E>, mut collection: ResMut<CollectedMessages<E>>| {
+            collection.extend(messages.read().filter(|message| filter(message)).cloned());
+        },
+    );
+}
+
 #[derive(Debug)]
 pub struct MessageCollectorPlugin<E>(PhantomData<E>)
 where
@@ -37,14 +66,7 @@ impl<E: Message + Clone> Default for MessageCollectorPlugin<E> {
 impl<E: Message + Clone> Plugin for MessageCollectorPlugin<E> {
     #[cfg_attr(coverage_nightly, coverage(off))]
     fn build(&self, app: &mut App) {
-        app.add_message::<E>()
-            .init_resource::<CollectedMessages<E>>()
-            .add_systems(
-                PostUpdate,
-                |mut messages: MessageReader<E>, mut collection: ResMut<CollectedMessages<E>>| {
-                    collection.extend(messages.read().cloned());
-                },
-            );
+        collect_messages_in::<E>(app.world_mut(), |_| true);
     }
 }
 
@@ -60,31 +82,14 @@ where
 impl<E: Message + Clone + PartialEq> Plugin for MessageFilterPlugin<E> {
     #[cfg_attr(coverage_nightly, coverage(off))]
     fn build(&self, app: &mut App) {
-        app.add_message::<E>()
-            .init_resource::<CollectedMessages<E>>();
         match &self {
             MessageFilterPlugin::Only(message) => {
-                app.add_systems(PostUpdate, {
-                    let message = message.clone();
-                    move |mut messages: MessageReader<E>,
-                          mut collection: ResMut<CollectedMessages<E>>| {
-                        collection.extend(messages.read().filter(|ev| *ev == &message).cloned());
-                    }
-                });
+                let message = message.clone();
+                collect_messages_in(app.world_mut(), move |ev: &E| *ev == message);
             }
             MessageFilterPlugin::AnyOf(any_of_messages) => {
-                app.add_systems(PostUpdate, {
-                    let any_of_messages = any_of_messages.clone();
-                    move |mut messages: MessageReader<E>,
-                          mut collection: ResMut<CollectedMessages<E>>| {
-                        collection.extend(
-                            messages
-                                .read()
-                                .filter(|ev| any_of_messages.contains(ev))
-                                .cloned(),
-                        );
-                    }
-                });
+                let any_of_messages = any_of_messages.clone();
+                collect_messages_in(app.world_mut(), move |ev: &E| any_of_messages.contains(ev));
             }
         }
     }
@@ -138,6 +143,16 @@ mod tests {
             .has_length(emit_count);
     }
 
+    #[rstest]
+    #[should_panic(expected = "messages are collected already")]
+    fn test_collecting_twice_panics(
+        #[from(minimal_test_app)]
+        #[with(MessageCollectorPlugin::<NonEqMessage>::default())]
+        mut app: TestApp,
+    ) {
+        app.collect_messages::<NonEqMessage>();
+    }
+
     #[derive(Clone, Debug, Message, PartialEq)]
     enum CmpMessage {
         A,
@@ -0,0 +1,140 @@
+use std::{fmt::Debug, ops::RangeInclusive, time::Duration};
+
+use bevy_ecs::message::Message;
+use proptest::{
+    collection::{SizeRange, vec},
+    strategy::Strategy,
+    test_runner::{Config, TestRunner},
+};
+
+use crate::test_app::TestApp;
+#[cfg(doc)]
+use crate::traits::RunFrames;
+
+/// Messages to write before each frame, see [`RunFrames::run_message_frames`].
+pub fn message_frames<M: Message + Debug>(
+    message: impl Strategy<Value = M> + Clone,
+    per_frame: impl Into<SizeRange>,
+    frames: impl Into<SizeRange>,
+) -> impl Strategy<Value = Vec<Vec<M>>> {
+    vec(vec(message, per_frame), frames)
+}
+
+/// Buttons held during each frame, see [`RunFrames::run_input_frames`].
+pub fn input_frames<T: Debug>(
+    button: impl Strategy<Value = T> + Clone,
+    held: impl Into<SizeRange>,
+    frames: impl Into<SizeRange>,
+) -> impl Strategy<Value = Vec<Vec<T>>> {
+    vec(vec(button, held), frames)
+}
+
+/// The delta of each frame, see [`RunFrames::run_frame_times`].
+///
+/// Shrinks towards the shortest delta.
+pub fn frame_times(
+    deltas: RangeInclusive<Duration>,
+    frames: impl Into<SizeRange>,
+) -> impl Strategy<Value = Vec<Duration>> {
+    let micros = deltas.start().as_micros() as u64..=deltas.end().as_micros() as u64;
+    vec(micros.prop_map(Duration::from_micros), frames)
+}
+
+/// Checks `property` with [`Config::default`], see [`check_property_with`].
+#[track_caller]
+pub fn check_property<S: Strategy>(
+    fixture: impl Fn() -> TestApp,
+    strategy: S,
+    property: impl Fn(&mut TestApp, S::Value),
+) {
+    check_property_with(Config::default(), fixture, strategy, property);
+}
+
+/// Checks `property` on many values of `strategy`, each in a fresh app from
+/// `fixture`.
+///
+/// The property fails by panicking, e.g. in `assert_that!`. The failing value
+/// is shrunk, and the test panics with the smallest one found.
+///
+/// ```rust,ignore
+/// check_property(
+///     || minimal_test_app(score_plugin),
+///     message_frames(any::<u8>().prop_map(Scored), 0..4, 1..20),
+///     |app, frames| {
+///         let total = frames.iter().flatten().map(|scored| scored.0 as u32).sum();
+///         app.run_message_frames(frames);
+///         assert_that!(app.world().resource::<Score>().0).is_equal_to(total);
+///     },
+/// );
+/// ```
+#[track_caller]
+pub fn check_property_with<S: Strategy>(
+    config: Config,
+    fixture: impl Fn() -> TestApp,
+    strategy: S,
+    property: impl Fn(&mut TestApp, S::Value),
+) {
+    let result = TestRunner::new(config).run(&strategy, |value| {
+        let mut app = fixture();
+        property(&mut app, value);
+        Ok(())
+    });
+    if let Err(error) = result {
+        panic!("{error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, Update};
+    use bevy_ecs::{message::MessageReader, resource::Resource, system::ResMut};
+    use proptest::prelude::*;
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, traits::RunFrames};
+
+    #[derive(Message, Debug, Clone, Copy)]
+    struct Scored(u8);
+
+    #[derive(Resource, Default)]
+    struct Score(u32);
+
+    fn score_plugin(app: &mut App) {
+        app.add_message::<Scored>()
+            .init_resource::<Score>()
+            .add_systems(
+                Update,
+                |mut scored: MessageReader<Scored>, mut score: ResMut<Score>| {
+                    score.0 += scored.read().map(|scored| scored.0 as u32).sum::<u32>();
+                },
+            );
+    }
+
+    #[rstest]
+    fn test_check_property() {
+        check_property(
+            || minimal_test_app(score_plugin),
+            message_frames(any::<u8>().prop_map(Scored), 0..4, 1..8),
+            |app, frames| {
+                let total: u32 = frames.iter().flatten().map(|scored| scored.0 as u32).sum();
+                app.run_message_frames(frames);
+                assert_that!(app.world().resource::<Score>().0).is_equal_to(total);
+            },
+        );
+    }
+
+    #[rstest]
+    #[should_panic(expected = "minimal failing input")]
+    fn test_check_property_shrinks() {
+        check_property(
+            || minimal_test_app(score_plugin),
+            message_frames(any::<u8>().prop_map(Scored), 0..4, 1..8),
+            |app, frames| {
+                app.run_message_frames(frames);
+                assert_that!(app.world().resource::<Score>().0).is_less_than(100);
+            },
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use bevy_app::{App, Plugin, SubApp};
+use bevy_ecs::{entity::Entity, query::With, schedule::Schedule, world::World};
+use bevy_render::{ExtractSchedule, MainWorld, RenderApp, sync_world::TemporaryRenderEntity};
+
+/// Adds a render world that only runs the [`ExtractSchedule`], so extraction
+/// systems can be tested without a GPU.
+///
+/// The render world is added in [`Plugin::cleanup`], after every other plugin
+/// saw there's none and skipped its GPU setup, like without a backend. So
+/// only the extraction systems a test adds run, not bevy's own ones for e.g.
+/// cameras and meshes: those need the GPU resources that were skipped. Call
+/// `app.finish()` and `app.cleanup()` after adding it,
+/// `headless_render_test_app` already does.
+///
+/// Entities spawned in the render world should be [`TemporaryRenderEntity`],
+/// they're despawned before every extraction, like bevy does at the end of a
+/// frame.
+#[derive(Debug, Default)]
+pub struct ExtractionPlugin;
+
+impl Plugin for ExtractionPlugin {
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn build(&self, _app: &mut App) {}
+
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn cleanup(&self, app: &mut App) {
+        let mut render_app = SubApp::new();
+        render_app.add_schedule(Schedule::new(ExtractSchedule));
+        render_app.set_extract(extract);
+        app.insert_sub_app(RenderApp, render_app);
+    }
+}
+
+/// like bevy's, the main world is moved into [`MainWorld`] while the
+/// [`ExtractSchedule`] runs
+fn extract(main_world: &mut World, render_world: &mut World) {
+    let temporary = render_world
+        .query_filtered::<Entity, With<TemporaryRenderEntity>>()
+        .iter(render_world)
+        .collect::<Vec<_>>();
+    for entity in temporary {
+        render_world.despawn(entity);
+    }
+
+    let mut inserted = MainWorld::default();
+    std::mem::swap(main_world, &mut *inserted);
+    render_world.insert_resource(inserted);
+    render_world.run_schedule(ExtractSchedule);
+    let mut inserted = render_world.remove_resource::<MainWorld>().unwrap();
+    std::mem::swap(main_world, &mut *inserted);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        component::Component,
+        system::{Commands, Query},
+    };
+    use bevy_internal::camera::Camera3d;
+    use bevy_pbr::wireframe::{Wireframe, WireframePlugin};
+    use bevy_render::{Extract, camera::ExtractedCamera};
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::headless_render_test_app, test_app::TestApp};
+
+    #[derive(Component)]
+    struct ExtractedWireframe;
+
+    fn extract_wireframes(mut commands: Commands, wireframes: Extract<Query<&Wireframe>>) {
+        for _ in &wireframes {
+            commands.spawn((TemporaryRenderEntity, ExtractedWireframe));
+        }
+    }
+
+    #[rstest]
+    fn test_extraction_with_bevy_render_plugin(
+        #[from(headless_render_test_app)]
+        #[with(WireframePlugin::default())]
+        mut app: TestApp,
+    ) {
+        app.sub_app_mut(RenderApp)
+            .add_systems(ExtractSchedule, extract_wireframes);
+        app.world_mut().spawn(Wireframe);
+
+        app.update();
+
+        let render_world = app.sub_app_mut(RenderApp).world_mut();
+        let extracted = render_world
+            .query::<&ExtractedWireframe>()
+            .iter(render_world)
+            .count();
+        assert_that!(extracted).is_equal_to(1);
+    }
+
+    #[rstest]
+    fn test_extraction_skips_bevy_extraction(#[from(headless_render_test_app)] mut app: TestApp) {
+        app.world_mut().spawn(Camera3d::default());
+
+        app.update();
+
+        let render_world = app.sub_app_mut(RenderApp).world_mut();
+        let extracted = render_world
+            .query::<&ExtractedCamera>()
+            .iter(render_world)
+            .count();
+        assert_that!(extracted)
+            .named("cameras extracted by bevy")
+            .is_equal_to(0);
+    }
+}
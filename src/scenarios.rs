@@ -0,0 +1,545 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::{self, LineWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bevy_app::{App, First, Last, Plugin};
+use bevy_ecs::{
+    error::Result,
+    message::{Message, MessageReader},
+    resource::Resource,
+    schedule::IntoScheduleConfigs,
+    system::{Res, ResMut},
+    world::World,
+};
+use bevy_input::{
+    keyboard::KeyboardInput,
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+};
+use bevy_internal::{
+    reflect::TypePath,
+    time::{Real, Time, TimeSystems, TimeUpdateStrategy, Virtual},
+};
+use ron::error::SpannedError;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::test_app::{TestApp, start_real_time};
+
+/// A recorded session, one [`RecordedFrame`] per update.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub frames: Vec<RecordedFrame>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// the real time since the last frame
+    pub delta: Duration,
+    /// the virtual time since the last frame, after its clamping, speed and
+    /// pausing
+    pub virtual_delta: Duration,
+    /// what arrived before the frame, in order
+    pub messages: Vec<RecordedMessage>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMessage {
+    /// the key the type was recorded with, its [`TypePath::type_path`] by
+    /// default
+    pub message_type: String,
+    pub ron: String,
+}
+
+#[derive(Debug)]
+pub enum ScenarioError {
+    Io(io::Error),
+    Parse {
+        line: usize,
+        error: Box<SpannedError>,
+    },
+    /// the replayer doesn't know the type, see [`ScenarioReplayer::replay`]
+    Unregistered {
+        message_type: String,
+    },
+    Deserialize {
+        message_type: String,
+        error: Box<SpannedError>,
+    },
+}
+
+impl Display for ScenarioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "can't read the scenario: {error}"),
+            Self::Parse { line, error } => write!(f, "invalid frame on line {line}: {error}"),
+            Self::Unregistered { message_type } => write!(
+                f,
+                "`{message_type}` isn't replayed, use `ScenarioReplayer::replay` or `replay_as`"
+            ),
+            Self::Deserialize {
+                message_type,
+                error,
+            } => write!(f, "invalid `{message_type}`: {error}"),
+        }
+    }
+}
+
+impl Error for ScenarioError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse { error, .. } => Some(error),
+            Self::Deserialize { error, .. } => Some(error.as_ref()),
+            Self::Unregistered { .. } => None,
+        }
+    }
+}
+
+impl Scenario {
+    /// Reads a file written by the [`ScenarioRecorderPlugin`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ScenarioError> {
+        Self::parse(&fs::read_to_string(path).map_err(ScenarioError::Io)?)
+    }
+
+    pub fn parse(recording: &str) -> Result<Self, ScenarioError> {
+        let frames = recording
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                ron::from_str(line).map_err(|error| ScenarioError::Parse {
+                    line: index + 1,
+                    error: Box::new(error),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { frames })
+    }
+}
+
+/// Records the messages injected before each frame, and the time since the
+/// last frame, to a file, one line per frame.
+///
+/// Messages of a type from [`Self::record`] are recorded when they're written
+/// with [`WriteMessages`](crate::traits::WriteMessages), not when systems
+/// write them: those would be written twice when replayed. Input comes from
+/// outside the app, [`Self::record_input`] records every input message.
+///
+/// ```rust,ignore
+/// app.add_plugins(
+///     ScenarioRecorderPlugin::new("qa/session.ron")
+///         .record_input()
+///         .record::<ChatMessage>(),
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ScenarioRecorderPlugin {
+    path: PathBuf,
+    injected: HashMap<TypeId, RecordInjected>,
+    readers: Vec<fn(&mut App)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct RecordInjected {
+    key: &'static str,
+    serialize: fn(&dyn Any) -> Result<String, ron::Error>,
+}
+
+impl ScenarioRecorderPlugin {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            injected: HashMap::new(),
+            readers: Vec::new(),
+        }
+    }
+
+    /// Records the messages written with `WriteMessages`, keyed by their
+    /// [`TypePath::type_path`].
+    pub fn record<M: Message + Serialize + TypePath>(self) -> Self {
+        self.record_as::<M>(M::type_path())
+    }
+
+    /// Like [`Self::record`], keyed by `key`, which
+    /// [`ScenarioReplayer::replay_as`] has to use too.
+    pub fn record_as<M: Message + Serialize>(mut self, key: &'static str) -> Self {
+        let serialize = |message: &dyn Any| {
+            ron::to_string(message.downcast_ref::<M>().expect("recorded by type id"))
+        };
+        self.injected
+            .insert(TypeId::of::<M>(), RecordInjected { key, serialize });
+        self
+    }
+
+    /// keyboard and mouse messages, however they're written
+    pub fn record_input(mut self) -> Self {
+        self.readers.extend([
+            read_messages::<KeyboardInput> as fn(&mut App),
+            read_messages::<MouseButtonInput>,
+            read_messages::<MouseMotion>,
+            read_messages::<MouseWheel>,
+        ]);
+        self
+    }
+}
+
+fn read_messages<M: Message + Serialize + TypePath>(app: &mut App) {
+    app.add_systems(First, record_messages::<M>.after(start_frame));
+}
+
+impl Plugin for ScenarioRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        let file = File::create(&self.path).unwrap_or_else(|error| {
+            panic!(
+                "can't create the scenario file `{}`: {error}",
+                self.path.display()
+            )
+        });
+        app.insert_resource(Recording {
+            file: LineWriter::new(file),
+            frame: RecordedFrame::default(),
+            injected: self.injected.clone(),
+        })
+        .add_systems(First, start_frame.after(TimeSystems))
+        .add_systems(Last, write_frame);
+        for reader in &self.readers {
+            reader(app);
+        }
+    }
+}
+
+#[derive(Resource)]
+struct Recording {
+    file: LineWriter<File>,
+    frame: RecordedFrame,
+    injected: HashMap<TypeId, RecordInjected>,
+}
+
+/// Whether `M` is recorded when written with `WriteMessages`, see
+/// [`record_injected`].
+pub(crate) fn records_injected<M: Message>(world: &World) -> bool {
+    world
+        .get_resource::<Recording>()
+        .is_some_and(|recording| recording.injected.contains_key(&TypeId::of::<M>()))
+}
+
+/// Records a message written with `WriteMessages`, if its type is recorded.
+pub(crate) fn record_injected<M: Message>(world: &mut World, message: &M) {
+    let Some(mut recording) = world.get_resource_mut::<Recording>() else {
+        return;
+    };
+    let Some(RecordInjected { key, serialize }) =
+        recording.injected.get(&TypeId::of::<M>()).copied()
+    else {
+        return;
+    };
+    let ron = serialize(message).unwrap_or_else(|error| panic!("can't record `{key}`: {error}"));
+    recording.frame.messages.push(RecordedMessage {
+        message_type: key.to_string(),
+        ron,
+    });
+}
+
+fn start_frame(
+    real_time: Res<Time<Real>>,
+    virtual_time: Res<Time<Virtual>>,
+    mut recording: ResMut<Recording>,
+) {
+    recording.frame.delta = real_time.delta();
+    recording.frame.virtual_delta = virtual_time.delta();
+}
+
+fn record_messages<M: Message + Serialize + TypePath>(
+    mut messages: MessageReader<M>,
+    mut recording: ResMut<Recording>,
+) -> Result {
+    for message in messages.read() {
+        let message = RecordedMessage {
+            message_type: M::type_path().to_string(),
+            ron: ron::to_string(message)?,
+        };
+        recording.frame.messages.push(message);
+    }
+    Ok(())
+}
+
+fn write_frame(mut recording: ResMut<Recording>) -> Result {
+    let frame = std::mem::take(&mut recording.frame);
+    writeln!(recording.file, "{}", ron::to_string(&frame)?)?;
+    Ok(())
+}
+
+type WriteRecorded = fn(&mut World, &str) -> Result<(), SpannedError>;
+
+/// Drives an app through a [`Scenario`], frame by frame: the recorded
+/// messages are written, then the app updates with the recorded real and
+/// virtual deltas. The app's own `max_delta` is kept, the recorded virtual
+/// delta is already clamped.
+///
+/// ```rust,ignore
+/// let replayer = ScenarioReplayer::load("qa/session.ron")
+///     .unwrap()
+///     .replay_input()
+///     .replay::<ChatMessage>();
+/// replayer.run(&mut app).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ScenarioReplayer {
+    scenario: Scenario,
+    writers: HashMap<&'static str, WriteRecorded>,
+}
+
+impl ScenarioReplayer {
+    pub fn new(scenario: Scenario) -> Self {
+        Self {
+            scenario,
+            writers: HashMap::new(),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ScenarioError> {
+        Scenario::load(path).map(Self::new)
+    }
+
+    /// Replays the messages recorded with [`ScenarioRecorderPlugin::record`].
+    pub fn replay<M: Message + DeserializeOwned + TypePath>(self) -> Self {
+        self.replay_as::<M>(M::type_path())
+    }
+
+    /// Replays the messages recorded with [`ScenarioRecorderPlugin::record_as`].
+    pub fn replay_as<M: Message + DeserializeOwned>(mut self, key: &'static str) -> Self {
+        self.writers.insert(key, |world, text| {
+            world.write_message(ron::from_str::<M>(text)?);
+            Ok(())
+        });
+        self
+    }
+
+    /// keyboard and mouse messages
+    pub fn replay_input(self) -> Self {
+        self.replay::<KeyboardInput>()
+            .replay::<MouseButtonInput>()
+            .replay::<MouseMotion>()
+            .replay::<MouseWheel>()
+    }
+
+    pub fn scenario(&self) -> &Scenario {
+        &self.scenario
+    }
+
+    /// Stops at the first message that can't be replayed.
+    pub fn run(&self, app: &mut TestApp) -> Result<(), ScenarioError> {
+        let world = app.world_mut();
+        let previous = world.remove_resource::<TimeUpdateStrategy>();
+        start_real_time(world);
+        if !world.contains_resource::<ReplayedFrame>() {
+            world.insert_resource(ReplayedFrame(None));
+            app.add_systems(First, replay_virtual_delta.after(TimeSystems));
+        }
+        let result = self.run_frames(app);
+        let world = app.world_mut();
+        match previous {
+            Some(previous) => world.insert_resource(previous),
+            None => {
+                world.remove_resource::<TimeUpdateStrategy>();
+            }
+        }
+        result
+    }
+
+    fn run_frames(&self, app: &mut TestApp) -> Result<(), ScenarioError> {
+        for frame in &self.scenario.frames {
+            let world = app.world_mut();
+            for message in &frame.messages {
+                let Some(write) = self.writers.get(message.message_type.as_str()) else {
+                    return Err(ScenarioError::Unregistered {
+                        message_type: message.message_type.clone(),
+                    });
+                };
+                write(world, &message.ron).map_err(|error| ScenarioError::Deserialize {
+                    message_type: message.message_type.clone(),
+                    error: Box::new(error),
+                })?;
+            }
+            let virtual_time = *world.resource::<Time<Virtual>>();
+            world.insert_resource(ReplayedFrame(Some((virtual_time, frame.virtual_delta))));
+            world.insert_resource(TimeUpdateStrategy::ManualDuration(frame.delta));
+            app.update();
+        }
+        Ok(())
+    }
+}
+
+/// the virtual time before the replayed frame, and its recorded delta
+#[derive(Resource)]
+struct ReplayedFrame(Option<(Time<Virtual>, Duration)>);
+
+/// advances the virtual time by the recorded delta, instead of the one
+/// derived from the real delta
+fn replay_virtual_delta(
+    mut replayed: ResMut<ReplayedFrame>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut time: ResMut<Time>,
+) {
+    let Some((before, delta)) = replayed.0.take() else {
+        return;
+    };
+    *virtual_time = before;
+    virtual_time.advance_by(delta);
+    *time = virtual_time.as_generic();
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::reflect::TypePath;
+    use bevy_app::{PreUpdate, Update};
+    use bevy_ecs::message::MessageWriter;
+    use rstest::{fixture, rstest};
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, traits::WriteMessages};
+
+    #[derive(Message, Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TypePath)]
+    struct Scored(u8);
+
+    #[derive(Resource, Default)]
+    struct Score(u32);
+
+    fn score_plugin(app: &mut App) {
+        app.add_message::<Scored>()
+            .init_resource::<Score>()
+            .add_systems(
+                Update,
+                |mut scored: MessageReader<Scored>, mut score: ResMut<Score>| {
+                    score.0 += scored.read().map(|scored| scored.0 as u32).sum::<u32>();
+                },
+            );
+    }
+
+    fn recording_path() -> PathBuf {
+        let test = std::thread::current()
+            .name()
+            .unwrap_or("main")
+            .replace("::", "-");
+        std::env::temp_dir().join(format!(
+            "rmv-bevy-testing-tools-{}-{test}.ron",
+            std::process::id()
+        ))
+    }
+
+    fn load_recording(app: TestApp, path: PathBuf) -> Scenario {
+        // the last update of the drop is recorded too
+        drop(app);
+        let scenario = Scenario::load(&path).unwrap();
+        fs::remove_file(path).unwrap();
+        scenario
+    }
+
+    /// a session with 3 frames, at double speed
+    #[fixture]
+    fn recorded() -> Scenario {
+        let path = recording_path();
+        let mut app = minimal_test_app((
+            score_plugin,
+            ScenarioRecorderPlugin::new(&path).record::<Scored>(),
+        ));
+        app.world_mut()
+            .resource_mut::<Time<Virtual>>()
+            .set_relative_speed(2.0);
+        app.write_message(Scored(2));
+        app.write_message_batch([Scored(3)]);
+        app.update_with_delta(Duration::from_millis(16));
+        app.update_with_delta(Duration::from_millis(500));
+        app.write_message(Scored(5));
+        app.update_with_delta(Duration::from_millis(16));
+        assert_that!(app.world().resource::<Score>().0).is_equal_to(10);
+        load_recording(app, path)
+    }
+
+    #[rstest]
+    fn test_record_scenario(recorded: Scenario) {
+        let frames = recorded
+            .frames
+            .iter()
+            .take(3)
+            .map(|frame| {
+                (
+                    frame.delta.as_millis(),
+                    frame.virtual_delta.as_millis(),
+                    frame.messages.len(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_that!(frames).is_equal_to(vec![(16, 32, 2), (500, 1000, 0), (16, 32, 1)]);
+        assert_that!(recorded.frames[0].messages[0]).is_equal_to(RecordedMessage {
+            message_type: Scored::type_path().to_string(),
+            ron: "(2)".to_string(),
+        });
+    }
+
+    #[rstest]
+    fn test_record_only_injected_messages() {
+        let path = recording_path();
+        let mut app = minimal_test_app((
+            score_plugin,
+            ScenarioRecorderPlugin::new(&path).record_as::<Scored>("scored"),
+        ));
+        app.add_systems(PreUpdate, |mut scored: MessageWriter<Scored>| {
+            scored.write(Scored(1));
+        });
+
+        app.write_message(Scored(2));
+        app.update();
+        let recorded = load_recording(app, path);
+
+        assert_that!(recorded.frames[0].messages).is_equal_to(vec![RecordedMessage {
+            message_type: "scored".to_string(),
+            ron: "(2)".to_string(),
+        }]);
+    }
+
+    #[rstest]
+    fn test_replay_scenario(recorded: Scenario, #[from(minimal_test_app)] mut app: TestApp) {
+        app.add_plugins(score_plugin);
+        let virtual_elapsed = recorded
+            .frames
+            .iter()
+            .map(|frame| frame.virtual_delta)
+            .sum::<Duration>();
+
+        ScenarioReplayer::new(recorded)
+            .replay::<Scored>()
+            .run(&mut app)
+            .unwrap();
+
+        assert_that!(app.world().resource::<Score>().0).is_equal_to(10);
+        let time = app.world().resource::<Time<Virtual>>();
+        assert_that!(time.elapsed()).is_equal_to(virtual_elapsed);
+        assert_that!(time.max_delta()).is_equal_to(Time::<Virtual>::default().max_delta());
+        assert_that!(matches!(
+            app.world().resource::<TimeUpdateStrategy>(),
+            TimeUpdateStrategy::Automatic
+        ))
+        .named("the strategy before the replay")
+        .is_true();
+    }
+
+    #[rstest]
+    fn test_replay_unregistered(recorded: Scenario, #[from(minimal_test_app)] mut app: TestApp) {
+        let error = ScenarioReplayer::new(recorded)
+            .replay_as::<Scored>("scored")
+            .run(&mut app)
+            .err()
+            .unwrap();
+
+        assert_that!(error.to_string()).contains("isn't replayed");
+    }
+}
@@ -0,0 +1,216 @@
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+    sync::{Arc, Mutex},
+};
+
+use bevy_app::{App, First, Plugin};
+use bevy_ecs::{
+    error::{BevyError, ErrorContext, FallbackErrorHandler},
+    resource::Resource,
+    schedule::Schedules,
+    system::Res,
+    world::World,
+};
+
+use crate::executor::run_single_threaded;
+
+/// An error returned by a fallible system, command, observer or run condition.
+#[derive(Clone, Debug)]
+pub struct SystemError {
+    pub error: Arc<BevyError>,
+    pub context: ErrorContext,
+}
+
+impl Display for SystemError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.context, self.error)
+    }
+}
+
+#[derive(Debug, Default)]
+struct SystemErrorLog {
+    errors: Vec<SystemError>,
+    acknowledged: usize,
+}
+
+/// Every error handled by the app, shared with the error handler.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct CapturedSystemErrors(Arc<Mutex<SystemErrorLog>>);
+
+impl CapturedSystemErrors {
+    pub fn get(&self) -> Vec<SystemError> {
+        self.0.lock().unwrap().errors.clone()
+    }
+
+    pub fn unacknowledged(&self) -> Vec<SystemError> {
+        let log = self.0.lock().unwrap();
+        log.errors[log.acknowledged..].to_vec()
+    }
+
+    /// returns the errors that weren't acknowledged yet
+    pub fn acknowledge(&self) -> Vec<SystemError> {
+        let mut log = self.0.lock().unwrap();
+        let unacknowledged = log.errors[log.acknowledged..].to_vec();
+        log.acknowledged = log.errors.len();
+        unacknowledged
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.lock().unwrap().errors.len()
+    }
+
+    /// Like [`Self::acknowledge`], but leaves out the errors of the final
+    /// update, from `final_update` on, that repeat an acknowledged one, like
+    /// those of a system failing every frame.
+    pub(crate) fn acknowledge_at_shutdown(&self, final_update: usize) -> Vec<SystemError> {
+        let mut log = self.0.lock().unwrap();
+        let acknowledged = log.errors[..log.acknowledged]
+            .iter()
+            .map(ToString::to_string)
+            .collect::<HashSet<_>>();
+        let unacknowledged = log.errors[log.acknowledged..]
+            .iter()
+            .enumerate()
+            .filter(|(index, error)| {
+                log.acknowledged + index < final_update
+                    || !acknowledged.contains(&error.to_string())
+            })
+            .map(|(_, error)| error.clone())
+            .collect();
+        log.acknowledged = log.errors.len();
+        unacknowledged
+    }
+
+    fn push(&self, error: SystemError) {
+        self.0.lock().unwrap().errors.push(error);
+    }
+}
+
+thread_local! {
+    static CURRENT_ERRORS: RefCell<Option<CapturedSystemErrors>> = const { RefCell::new(None) };
+}
+
+/// Replaces the default error handler with one recording into
+/// [`CapturedSystemErrors`]. `TestApp` panics on drop while any of them is
+/// unacknowledged, except for repeats of acknowledged errors in its final
+/// update.
+#[derive(Debug, Default)]
+pub struct SystemErrorCapturePlugin;
+
+impl Plugin for SystemErrorCapturePlugin {
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn build(&self, app: &mut App) {
+        capture_system_errors_in(app.world_mut());
+    }
+}
+
+pub(crate) fn capture_system_errors_in(world: &mut World) {
+    if world.contains_resource::<CapturedSystemErrors>() {
+        return;
+    }
+    let errors = CapturedSystemErrors::default();
+    CURRENT_ERRORS.set(Some(errors.clone()));
+    world.insert_resource(errors);
+    world.insert_resource(FallbackErrorHandler(record_system_error));
+    world.get_resource_or_init::<Schedules>().add_systems(
+        First,
+        |errors: Res<CapturedSystemErrors>| {
+            // several apps might share a thread, only one updates at a time
+            CURRENT_ERRORS.set(Some(errors.clone()));
+        },
+    );
+    run_single_threaded(world);
+}
+
+fn record_system_error(error: BevyError, context: ErrorContext) {
+    match CURRENT_ERRORS.with_borrow(Clone::clone) {
+        Some(errors) => errors.push(SystemError {
+            error: Arc::new(error),
+            context,
+        }),
+        // not on a test thread, behave like bevy's default
+        None => bevy_ecs::error::panic(error, context),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{AppExit, PostUpdate, Update};
+    use bevy_ecs::{error::Result, message::MessageReader};
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp, traits::CaptureSystemErrors};
+
+    fn failing_system() -> Result {
+        Err("boom".into())
+    }
+
+    #[rstest]
+    fn test_system_error_capture_plugin(
+        #[from(minimal_test_app)]
+        #[with(SystemErrorCapturePlugin)]
+        mut app: TestApp,
+    ) {
+        app.add_systems(Update, failing_system);
+
+        app.update();
+        app.update();
+
+        let errors = app.world().resource::<CapturedSystemErrors>().clone();
+        assert_that!(errors.get()).has_length(2);
+        assert_that!(errors.acknowledge()).has_length(2);
+        assert_that!(errors.unacknowledged()).is_empty();
+        assert_that!(errors.get()[0].to_string()).contains("boom");
+    }
+
+    #[rstest]
+    #[should_panic(expected = "unacknowledged system error(s)")]
+    fn test_unacknowledged_system_errors_panic_on_drop(
+        #[from(minimal_test_app)]
+        #[with(SystemErrorCapturePlugin)]
+        mut app: TestApp,
+    ) {
+        app.add_systems(Update, failing_system);
+
+        app.update();
+    }
+
+    #[rstest]
+    #[should_panic(expected = "unacknowledged system error(s)")]
+    fn test_unacknowledged_system_errors_panic_on_drop_after_caught_panic(
+        #[from(minimal_test_app)]
+        #[with(SystemErrorCapturePlugin)]
+        mut app: TestApp,
+    ) {
+        app.add_systems(Update, failing_system)
+            .add_systems(PostUpdate, || panic!("oh no"));
+
+        assert_that!(app.try_update()).is_err();
+    }
+
+    #[rstest]
+    #[should_panic(expected = "failed: can't save on exit")]
+    fn test_new_errors_of_the_final_update_panic_on_drop(
+        #[from(minimal_test_app)]
+        #[with(SystemErrorCapturePlugin)]
+        mut app: TestApp,
+    ) {
+        app.add_systems(Update, failing_system).add_systems(
+            Update,
+            |mut exits: MessageReader<AppExit>| -> Result {
+                match exits.read().next() {
+                    Some(_) => Err("can't save on exit".into()),
+                    None => Ok(()),
+                }
+            },
+        );
+
+        app.update();
+
+        assert_that!(app.acknowledge_system_errors()).has_length(1);
+    }
+}
@@ -1,13 +1,465 @@
-use bevy_app::{App, AppExit};
+use std::time::Duration;
+
+use bevy_app::{App, AppExit, AppLabel, Main, SubApp, SubApps};
 use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{schedule::Schedules, world::World};
+use bevy_internal::time::{Real, Time, TimeUpdateStrategy, Virtual};
+
+#[cfg(feature = "logs")]
+use crate::logs::{CapturedLog, CapturedLogs, ErrorLogCheck, release_logs};
+#[cfg(feature = "trait_capture_system_errors")]
+use crate::system_errors::{CapturedSystemErrors, SystemError};
+use crate::update_panic::{self, CaughtUpdatePanic, UpdatePanic};
 
-// TODO: remove, impl traits on bevy app directly?
+/// An [`App`] that shuts down cleanly when dropped.
+///
+/// The crate's traits work on any [`AsWorld`](crate::as_world::AsWorld),
+/// `TestApp` is just the most convenient one.
 #[derive(Debug, Deref, DerefMut)]
 pub struct TestApp(pub App);
 
-// NOTE: this is now also handled by feature `bevy/bevy_ci_testing`
+/// What's left of a [`TestApp`] after [`TestApp::shutdown`].
+#[derive(Debug)]
+pub struct Shutdown {
+    pub exit: AppExit,
+    /// the panic that ended the app, if any
+    pub panic: Option<UpdatePanic>,
+    /// errors that weren't acknowledged before the shutdown
+    #[cfg(feature = "trait_capture_system_errors")]
+    pub system_errors: Vec<SystemError>,
+    /// logs the `PanicOnErrorLogPlugin` hadn't checked yet, including those of
+    /// the final update and of dropping the app
+    #[cfg(feature = "logs")]
+    pub unexpected_logs: Vec<CapturedLog>,
+}
+
+impl TestApp {
+    /// Like [`App::update`], but catches a panic and reports which system
+    /// caused it.
+    ///
+    /// From the first call on, all schedules run on the calling thread, and a
+    /// panic hook stays installed for the rest of the process. It hands every
+    /// panic it doesn't catch to the previous hook.
+    pub fn try_update(&mut self) -> Result<(), UpdatePanic> {
+        update_panic::try_update(self)
+    }
+
+    /// Runs `frames` updates, panics unless `condition` first holds after the
+    /// last of them.
+    #[track_caller]
+    pub fn after_frames(
+        &mut self,
+        frames: u32,
+        mut condition: impl FnMut(&mut App) -> bool,
+    ) -> &mut Self {
+        for frame in 1..=frames {
+            self.update();
+            let met = condition(&mut self.0);
+            if frame < frames && met {
+                panic!("condition met after {frame} frame(s) instead of {frames}");
+            } else if frame == frames && !met {
+                panic!("condition not met after {frames} frame(s)");
+            }
+        }
+        self
+    }
+
+    /// Updates once, as if `delta` passed since the last frame. Needs bevy's
+    /// `TimePlugin`.
+    pub fn update_with_delta(&mut self, delta: Duration) -> &mut Self {
+        update_with_delta(self.sub_apps_mut(), delta);
+        self
+    }
+
+    /// Updates until `condition` holds and returns the number of updates it
+    /// took, panics if it doesn't hold after `frames` updates.
+    #[track_caller]
+    pub fn within_frames(
+        &mut self,
+        frames: u32,
+        mut condition: impl FnMut(&mut App) -> bool,
+    ) -> u32 {
+        for frame in 1..=frames {
+            self.update();
+            if condition(&mut self.0) {
+                return frame;
+            }
+        }
+        panic!("condition not met within {frames} frame(s)");
+    }
+
+    /// Runs `f` with the sub-app `label`, so the crate's traits can work on
+    /// its world instead of the main one.
+    ///
+    /// Panics if there is no such sub-app.
+    pub fn sub_app_scope<R>(
+        &mut self,
+        label: impl AppLabel,
+        f: impl FnOnce(&mut SubApp) -> R,
+    ) -> R {
+        f(self.sub_app_mut(label))
+    }
+
+    /// Runs the extract function of the sub-app `label` on the main world,
+    /// without updating either of them.
+    ///
+    /// [`App::update_sub_app_by_label`] extracts and updates the sub-app.
+    /// Panics if there is no such sub-app.
+    pub fn extract_sub_app(&mut self, label: impl AppLabel) {
+        let label = label.intern();
+        let mut sub_app = self
+            .remove_sub_app(label)
+            .unwrap_or_else(|| panic!("No sub-app with label '{label:?}' exists."));
+        sub_app.extract(self.world_mut());
+        self.insert_sub_app(label, sub_app);
+    }
+
+    /// Writes [`AppExit::Success`] (unless the app is exiting already) and runs
+    /// a final update so every system sees it, then drops the app.
+    ///
+    /// Dropping a `TestApp` does the same, but panics on leftover problems
+    /// instead of returning them. (This isn't called `finish` so that
+    /// [`App::finish`] stays available.)
+    pub fn shutdown(mut self) -> Shutdown {
+        self.teardown()
+    }
+
+    /// `run()`, `shutdown()` and unwinding panics leave no schedules to run
+    fn is_running(&self) -> bool {
+        self.world()
+            .get_resource::<Schedules>()
+            .is_some_and(|schedules| schedules.contains(Main))
+    }
+
+    fn teardown(&mut self) -> Shutdown {
+        let mut panic = self
+            .world()
+            .get_resource::<CaughtUpdatePanic>()
+            .map(|caught| caught.0.clone());
+        // errors from here on may repeat acknowledged ones
+        #[cfg(feature = "trait_capture_system_errors")]
+        let final_update = self
+            .world()
+            .get_resource::<CapturedSystemErrors>()
+            .map(CapturedSystemErrors::len)
+            .unwrap_or_default();
+        if panic.is_none() && self.is_running() {
+            if self.should_exit().is_none() {
+                self.world_mut().write_message(AppExit::Success);
+            }
+            // a panic hook or new executors would outlive the app
+            panic = update_panic::catch_update(self).err();
+        }
+        let exit = match panic {
+            Some(_) => AppExit::error(),
+            None => self.should_exit().unwrap_or(AppExit::Success),
+        };
+        #[cfg(feature = "trait_capture_system_errors")]
+        let system_errors = self
+            .world()
+            .get_resource::<CapturedSystemErrors>()
+            .map(|errors| errors.acknowledge_at_shutdown(final_update))
+            .unwrap_or_default();
+        #[cfg(feature = "logs")]
+        let log_check = self.world_mut().remove_resource::<ErrorLogCheck>();
+        #[cfg(feature = "logs")]
+        let logs = self.world().get_resource::<CapturedLogs>().cloned();
+
+        // drops the plugins' resources, task pools, etc.
+        self.0 = App::empty();
+
+        // the next app on this thread starts with its own logs
+        #[cfg(feature = "logs")]
+        let unexpected_logs = logs
+            .inspect(release_logs)
+            .zip(log_check)
+            .map(|(logs, mut check)| check.unexpected(&logs))
+            .unwrap_or_default();
+        Shutdown {
+            exit,
+            panic,
+            #[cfg(feature = "trait_capture_system_errors")]
+            system_errors,
+            #[cfg(feature = "logs")]
+            unexpected_logs,
+        }
+    }
+}
+
+/// Lets the next updates advance the time by `delta`, with a manual
+/// [`TimeUpdateStrategy`].
+fn allow_time_delta(world: &mut World, delta: Duration) {
+    start_real_time(world);
+    allow_virtual_delta(&mut world.resource_mut::<Time<Virtual>>(), delta);
+}
+
+/// virtual time would clamp a large delta
+pub(crate) fn allow_virtual_delta(virtual_time: &mut Time<Virtual>, delta: Duration) {
+    if virtual_time.max_delta() < delta {
+        virtual_time.set_max_delta(delta);
+    }
+}
+
+/// the very first update has no delta otherwise
+pub(crate) fn start_real_time(world: &mut World) {
+    let mut real_time = world.resource_mut::<Time<Real>>();
+    if real_time.first_update().is_none() {
+        let startup = real_time.startup();
+        real_time.update_with_instant(startup);
+    }
+}
+
+/// [`TestApp::update_with_delta`], for the traits working on any app
+pub(crate) fn update_with_delta(sub_apps: &mut SubApps, delta: Duration) {
+    let world = sub_apps.main.world_mut();
+    let previous = world.remove_resource::<TimeUpdateStrategy>();
+    allow_time_delta(world, delta);
+    world.insert_resource(TimeUpdateStrategy::ManualDuration(delta));
+    sub_apps.update();
+    let world = sub_apps.main.world_mut();
+    match previous {
+        Some(previous) => world.insert_resource(previous),
+        None => {
+            world.remove_resource::<TimeUpdateStrategy>();
+        }
+    }
+}
+
 impl Drop for TestApp {
     fn drop(&mut self) {
-        self.world_mut().write_message(AppExit::Success);
+        // a panicking test already failed
+        if std::thread::panicking() {
+            return;
+        }
+        // the test has seen a panic caught by `try_update` already, but not
+        // the errors and logs that came before it
+        let caught = self.world().contains_resource::<CaughtUpdatePanic>();
+
+        let shutdown = self.teardown();
+
+        if let Some(panic) = shutdown.panic
+            && !caught
+        {
+            panic!("{panic}");
+        }
+        #[cfg(feature = "trait_capture_system_errors")]
+        if !shutdown.system_errors.is_empty() {
+            let errors = shutdown
+                .system_errors
+                .iter()
+                .map(|error| format!("  {error}"))
+                .collect::<Vec<_>>();
+            panic!(
+                "{} unacknowledged system error(s):\n{}",
+                errors.len(),
+                errors.join("\n")
+            );
+        }
+        #[cfg(feature = "logs")]
+        if !shutdown.unexpected_logs.is_empty() {
+            let logs = shutdown
+                .unexpected_logs
+                .iter()
+                .map(|log| format!("  {log}"))
+                .collect::<Vec<_>>();
+            panic!(
+                "{} unexpected log(s) during the shutdown:\n{}",
+                logs.len(),
+                logs.join("\n")
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    use bevy_app::{Last, Update};
+    use bevy_ecs::{
+        message::{Message, MessageReader, MessageWriter},
+        resource::Resource,
+        system::ResMut,
+    };
+    use rstest::{fixture, rstest};
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::fixtures::minimal_test_app;
+
+    fn panicking_system() {
+        panic!("oh no");
+    }
+
+    #[rstest]
+    fn test_shutdown_runs_last_with_app_exit(#[from(minimal_test_app)] mut app: TestApp) {
+        let exit_seen = Arc::new(AtomicBool::new(false));
+        app.add_systems(Last, {
+            let exit_seen = exit_seen.clone();
+            move |mut exits: MessageReader<AppExit>| {
+                if exits.read().next().is_some() {
+                    exit_seen.store(true, Ordering::SeqCst);
+                }
+            }
+        });
+        app.update();
+
+        let shutdown = app.shutdown();
+
+        assert_that!(shutdown.exit).is_equal_to(AppExit::Success);
+        assert_that!(shutdown.panic.is_none()).is_true();
+        assert_that!(exit_seen.load(Ordering::SeqCst))
+            .named("AppExit seen in Last")
+            .is_true();
+    }
+
+    #[rstest]
+    fn test_shutdown_keeps_existing_exit(#[from(minimal_test_app)] mut app: TestApp) {
+        app.add_systems(Update, |mut exits: MessageWriter<AppExit>| {
+            exits.write(AppExit::from_code(3));
+        });
+        app.update();
+
+        assert_that!(app.shutdown().exit).is_equal_to(AppExit::from_code(3));
+    }
+
+    #[rstest]
+    fn test_shutdown_reports_caught_panic(#[from(minimal_test_app)] mut app: TestApp) {
+        app.add_systems(Update, panicking_system);
+        let _ = app.try_update();
+
+        let shutdown = app.shutdown();
+
+        assert_that!(shutdown.exit).is_equal_to(AppExit::error());
+        assert_that!(shutdown.panic.map(|panic| panic.message))
+            .is_equal_to(Some("oh no".to_string()));
+    }
+
+    #[rstest]
+    fn test_shutdown_reports_final_update_panic(#[from(minimal_test_app)] mut app: TestApp) {
+        let mut updates = 0;
+        app.add_systems(Update, move || {
+            updates += 1;
+            if updates == 2 {
+                panic!("oh no");
+            }
+        });
+        app.update();
+
+        let shutdown = app.shutdown();
+
+        assert_that!(shutdown.exit).is_equal_to(AppExit::error());
+        assert_that!(shutdown.panic.map(|panic| panic.message))
+            .is_equal_to(Some("oh no".to_string()));
+    }
+
+    #[rstest]
+    fn test_within_frames(#[from(minimal_test_app)] mut app: TestApp) {
+        app.init_resource::<Score>()
+            .add_systems(Update, |mut score: ResMut<Score>| score.0 += 1);
+
+        let frames = app.within_frames(10, |app| app.world().resource::<Score>().0 == 4);
+
+        assert_that!(frames).is_equal_to(4);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "condition not met within 3 frame(s)")]
+    fn test_within_frames_panics(#[from(minimal_test_app)] mut app: TestApp) {
+        app.update();
+        app.update();
+        app.within_frames(3, |_| false);
+    }
+
+    #[rstest]
+    fn test_after_frames(#[from(minimal_test_app)] mut app: TestApp) {
+        app.init_resource::<Score>()
+            .add_systems(Update, |mut score: ResMut<Score>| score.0 += 1);
+
+        app.after_frames(3, |app| app.world().resource::<Score>().0 >= 3);
+
+        assert_that!(app.world().resource::<Score>().0).is_equal_to(3);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "condition met after 2 frame(s) instead of 3")]
+    fn test_after_frames_panics_when_early(#[from(minimal_test_app)] mut app: TestApp) {
+        app.init_resource::<Score>()
+            .add_systems(Update, |mut score: ResMut<Score>| score.0 += 1);
+
+        app.after_frames(3, |app| app.world().resource::<Score>().0 >= 2);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "condition not met after 3 frame(s)")]
+    fn test_after_frames_panics_when_not_met(#[from(minimal_test_app)] mut app: TestApp) {
+        app.after_frames(3, |_| false);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "oh no")]
+    fn test_drop_panics_on_teardown_panic(#[from(minimal_test_app)] mut app: TestApp) {
+        let mut updates = 0;
+        app.add_systems(Update, move || {
+            updates += 1;
+            if updates == 2 {
+                panic!("oh no");
+            }
+        });
+        app.update();
+    }
+
+    #[derive(AppLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct MySubApp;
+
+    #[derive(Clone, Debug, Default, PartialEq, Resource)]
+    struct Score(u32);
+
+    #[derive(Clone, Debug, Message, PartialEq)]
+    struct MyMessage;
+
+    #[fixture]
+    fn sub_app_test_app(#[from(minimal_test_app)] mut app: TestApp) -> TestApp {
+        let mut sub_app = SubApp::new();
+        sub_app.set_extract(|main, sub| {
+            if let Some(score) = main.get_resource::<Score>() {
+                sub.insert_resource(score.clone());
+            }
+        });
+        app.insert_sub_app(MySubApp, sub_app);
+        app
+    }
+
+    #[rstest]
+    fn test_sub_app_scope(#[from(sub_app_test_app)] mut app: TestApp) {
+        use crate::traits::{CollectMessages, WriteMessages};
+
+        app.sub_app_scope(MySubApp, |sub_app| {
+            sub_app.collect_messages::<MyMessage>();
+            sub_app.write_message(MyMessage);
+            sub_app.world_mut().run_schedule(bevy_app::PostUpdate);
+        });
+
+        assert_that!(app.get_collected_messages::<MyMessage>())
+            .named("main app")
+            .is_none();
+        assert_that!(app.sub_app_scope(MySubApp, |sub_app| {
+            sub_app.get_collected_messages::<MyMessage>()
+        }))
+        .named("sub app")
+        .is_equal_to(Some(vec![MyMessage]));
+    }
+
+    #[rstest]
+    fn test_extract_sub_app(#[from(sub_app_test_app)] mut app: TestApp) {
+        app.insert_resource(Score(3));
+
+        app.extract_sub_app(MySubApp);
+
+        assert_that!(app.sub_app(MySubApp).world().get_resource::<Score>())
+            .is_equal_to(Some(&Score(3)));
     }
 }
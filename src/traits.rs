@@ -6,9 +6,37 @@ macro_rules! transparent_module {
     };
 }
 
+#[cfg(feature = "trait_assert_changes")]
+transparent_module!(assert_changes);
+#[cfg(feature = "trait_assert_hierarchy")]
+transparent_module!(assert_hierarchy);
+#[cfg(feature = "trait_audit_reflection")]
+transparent_module!(audit_reflection);
+#[cfg(feature = "trait_capture_logs")]
+transparent_module!(capture_logs);
+#[cfg(feature = "trait_capture_system_errors")]
+transparent_module!(capture_system_errors);
 #[cfg(any(all(test, feature = "rstest"), feature = "trait_collect_messages"))]
 transparent_module!(collect_messages);
+#[cfg(feature = "trait_count_frames")]
+transparent_module!(count_frames);
+#[cfg(feature = "trait_fixed_time")]
+transparent_module!(fixed_time);
 #[cfg(feature = "trait_manage_state")]
 transparent_module!(manage_state);
+#[cfg(feature = "trait_render_world")]
+transparent_module!(render_world);
+#[cfg(feature = "trait_run_frames")]
+transparent_module!(run_frames);
+#[cfg(feature = "trait_scene_round_trip")]
+transparent_module!(scene_round_trip);
+// not `transparent_module!`, its exported macros refer to each other by
+// `$crate::` path, which doesn't work for macro-expanded modules
+#[cfg(feature = "trait_spawn_test_entities")]
+pub mod spawn_test_entities;
+#[cfg(feature = "trait_spawn_test_entities")]
+pub use spawn_test_entities::*;
+#[cfg(feature = "trait_test_assets")]
+transparent_module!(test_assets);
 #[cfg(any(all(test, feature = "rstest"), feature = "trait_write_messages"))]
 transparent_module!(write_messages);
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use bevy_app::MainScheduleOrder;
+use bevy_ecs::{
+    change_detection::{ComponentTicks, Tick},
+    component::{Component, ComponentId},
+    entity::Entity,
+    resource::Resource,
+    schedule::{ScheduleLabel, Schedules},
+    world::World,
+};
+use bevy_utils::prelude::ShortName;
+
+use crate::as_world::AsWorld;
+
+/// What the world looked like when the last update started.
+#[derive(Debug, Resource)]
+pub struct ChangeTracking {
+    pub tick: Tick,
+    /// number of removals written so far, per component
+    removals: HashMap<ComponentId, usize>,
+    startup: bool,
+}
+
+impl ChangeTracking {
+    fn new(world: &World) -> Self {
+        Self {
+            tick: world.read_change_tick(),
+            removals: world
+                .removed_components()
+                .iter()
+                .map(|(id, messages)| (*id, messages.oldest_message_count() + messages.len()))
+                .collect(),
+            startup: false,
+        }
+    }
+}
+
+/// runs before the startup schedules of the first update
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct TrackStartupChanges;
+
+/// runs before `First`
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct TrackChanges;
+
+fn record_startup_start(world: &mut World) {
+    let tracking = ChangeTracking {
+        startup: true,
+        ..ChangeTracking::new(world)
+    };
+    world.insert_resource(tracking);
+}
+
+fn record_update_start(world: &mut World) {
+    // the first update started with the startup schedules
+    if !std::mem::take(&mut world.resource_mut::<ChangeTracking>().startup) {
+        let tracking = ChangeTracking::new(world);
+        world.insert_resource(tracking);
+    }
+}
+
+fn component_ticks<C: Component>(world: &World, entity: Entity) -> Option<ComponentTicks> {
+    world.get_entity(entity).ok()?.get_change_ticks::<C>()
+}
+
+#[track_caller]
+fn tracked_tick(world: &World) -> Tick {
+    world
+        .get_resource::<ChangeTracking>()
+        .expect("changes are not tracked, call `track_changes()` first")
+        .tick
+}
+
+#[doc = include_str!("./assert_changes.md")]
+pub trait AssertChanges {
+    fn track_changes(&mut self) -> &mut Self;
+    fn last_update_tick(&self) -> Option<Tick>;
+    fn is_added_since<C: Component>(&self, entity: Entity, tick: Tick) -> bool;
+    fn is_changed_since<C: Component>(&self, entity: Entity, tick: Tick) -> bool;
+    fn removed_last_update<C: Component>(&self) -> Option<Vec<Entity>>;
+    fn assert_added<C: Component>(&self, entity: Entity);
+    fn assert_changed<C: Component>(&self, entity: Entity);
+    fn assert_changed_since<C: Component>(&self, entity: Entity, tick: Tick);
+    fn assert_not_changed<C: Component>(&self, entity: Entity);
+    fn assert_removed<C: Component>(&self, entity: Entity);
+}
+
+impl<T: AsWorld> AssertChanges for T {
+    fn track_changes(&mut self) -> &mut Self {
+        let world = self.as_world_mut();
+        if !world.contains_resource::<ChangeTracking>() {
+            let tracking = ChangeTracking::new(world);
+            world.insert_resource(tracking);
+            let mut schedules = world.get_resource_or_init::<Schedules>();
+            schedules.add_systems(TrackStartupChanges, record_startup_start);
+            schedules.add_systems(TrackChanges, record_update_start);
+            if let Some(mut order) = world.get_resource_mut::<MainScheduleOrder>() {
+                order.startup_labels.insert(0, TrackStartupChanges.intern());
+                order.labels.insert(0, TrackChanges.intern());
+            }
+        }
+        self
+    }
+
+    fn last_update_tick(&self) -> Option<Tick> {
+        self.as_world()
+            .get_resource::<ChangeTracking>()
+            .map(|tracking| tracking.tick)
+    }
+
+    fn is_added_since<C: Component>(&self, entity: Entity, tick: Tick) -> bool {
+        let world = self.as_world();
+        component_ticks::<C>(world, entity)
+            .is_some_and(|ticks| ticks.is_added(tick, world.read_change_tick()))
+    }
+
+    fn is_changed_since<C: Component>(&self, entity: Entity, tick: Tick) -> bool {
+        let world = self.as_world();
+        component_ticks::<C>(world, entity)
+            .is_some_and(|ticks| ticks.is_changed(tick, world.read_change_tick()))
+    }
+
+    fn removed_last_update<C: Component>(&self) -> Option<Vec<Entity>> {
+        let world = self.as_world();
+        let tracking = world.get_resource::<ChangeTracking>()?;
+        let Some(id) = world.component_id::<C>() else {
+            return Some(Vec::new());
+        };
+        let Some(messages) = world.removed_components().get(id) else {
+            return Some(Vec::new());
+        };
+        let start = tracking.removals.get(&id).copied().unwrap_or_default();
+        let end = messages.oldest_message_count() + messages.len();
+        Some(
+            (start..end)
+                .filter_map(|id| messages.get_message(id))
+                .map(|(removed, _)| Entity::from(removed.clone()))
+                .collect(),
+        )
+    }
+
+    #[track_caller]
+    fn assert_added<C: Component>(&self, entity: Entity) {
+        let tick = tracked_tick(self.as_world());
+        if !self.is_added_since::<C>(entity, tick) {
+            panic!(
+                "expected {} to be added to {entity} since tick {}, {}",
+                ShortName::of::<C>(),
+                tick.get(),
+                describe_ticks(component_ticks::<C>(self.as_world(), entity)),
+            );
+        }
+    }
+
+    #[track_caller]
+    fn assert_changed<C: Component>(&self, entity: Entity) {
+        self.assert_changed_since::<C>(entity, tracked_tick(self.as_world()));
+    }
+
+    #[track_caller]
+    fn assert_changed_since<C: Component>(&self, entity: Entity, tick: Tick) {
+        if !self.is_changed_since::<C>(entity, tick) {
+            panic!(
+                "expected {} on {entity} to be changed since tick {}, {}",
+                ShortName::of::<C>(),
+                tick.get(),
+                describe_ticks(component_ticks::<C>(self.as_world(), entity)),
+            );
+        }
+    }
+
+    #[track_caller]
+    fn assert_not_changed<C: Component>(&self, entity: Entity) {
+        let tick = tracked_tick(self.as_world());
+        if self.is_changed_since::<C>(entity, tick) {
+            panic!(
+                "expected {} on {entity} to be unchanged since tick {}, {}",
+                ShortName::of::<C>(),
+                tick.get(),
+                describe_ticks(component_ticks::<C>(self.as_world(), entity)),
+            );
+        }
+    }
+
+    #[track_caller]
+    fn assert_removed<C: Component>(&self, entity: Entity) {
+        let removed = self
+            .removed_last_update::<C>()
+            .expect("changes are not tracked, call `track_changes()` first");
+        if !removed.contains(&entity) {
+            panic!(
+                "expected {} to be removed from {entity} during the last update, removed from: {removed:?}",
+                ShortName::of::<C>(),
+            );
+        }
+    }
+}
+
+fn describe_ticks(ticks: Option<ComponentTicks>) -> String {
+    match ticks {
+        Some(ticks) => format!(
+            "it was added at tick {} and changed at tick {}",
+            ticks.added.get(),
+            ticks.changed.get()
+        ),
+        None => "the entity doesn't have one".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{Startup, Update};
+    use bevy_ecs::{
+        query::With,
+        system::{Commands, Query, Single},
+    };
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    #[derive(Component, Debug, Default, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    struct Poisoned;
+
+    #[rstest]
+    fn assert_changes_trait_added_in_startup(#[from(minimal_test_app)] mut app: TestApp) {
+        app.track_changes()
+            .add_systems(Startup, |mut commands: Commands| {
+                commands.spawn(Health(10));
+            });
+
+        app.update();
+
+        let entity = app
+            .world_mut()
+            .query_filtered::<Entity, With<Health>>()
+            .single(app.world())
+            .unwrap();
+        app.assert_added::<Health>(entity);
+        app.assert_changed::<Health>(entity);
+
+        app.update();
+
+        app.assert_not_changed::<Health>(entity);
+    }
+
+    #[rstest]
+    fn assert_changes_trait_changed(#[from(minimal_test_app)] mut app: TestApp) {
+        let entity = app.world_mut().spawn((Health(10), Poisoned)).id();
+        app.track_changes().add_systems(
+            Update,
+            |mut health: Single<&mut Health, With<Poisoned>>| {
+                health.0 -= 1;
+            },
+        );
+
+        app.update();
+
+        app.assert_changed::<Health>(entity);
+        assert_that!(app.is_added_since::<Health>(entity, app.last_update_tick().unwrap()))
+            .named("added before the update")
+            .is_false();
+    }
+
+    #[rstest]
+    #[should_panic(expected = "to be unchanged since tick")]
+    fn assert_changes_trait_unnecessary_change(#[from(minimal_test_app)] mut app: TestApp) {
+        let entity = app.world_mut().spawn(Health(10)).id();
+        app.track_changes()
+            .add_systems(Update, |mut query: Query<&mut Health>| {
+                for mut health in &mut query {
+                    // should have used `set_if_neq`
+                    *health = Health(10);
+                }
+            });
+
+        app.update();
+
+        app.assert_not_changed::<Health>(entity);
+    }
+
+    #[rstest]
+    fn assert_changes_trait_removed(#[from(minimal_test_app)] mut app: TestApp) {
+        let entity = app.world_mut().spawn((Health(10), Poisoned)).id();
+        app.track_changes().add_systems(
+            Update,
+            |mut commands: Commands, query: Query<Entity, With<Poisoned>>| {
+                for entity in &query {
+                    commands.entity(entity).remove::<Poisoned>();
+                }
+            },
+        );
+
+        app.update();
+        app.assert_removed::<Poisoned>(entity);
+
+        app.update();
+        assert_that!(app.removed_last_update::<Poisoned>())
+            .named("nothing removed in the second update")
+            .is_equal_to(Some(Vec::new()));
+    }
+}
@@ -0,0 +1,198 @@
+use bevy_ecs::{
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    schedule::{IntoScheduleConfigs, ScheduleLabel, Schedules},
+    world::EntityRef,
+};
+use bevy_internal::transform::{
+    components::GlobalTransform,
+    systems::{
+        StaticTransformOptimizations, mark_dirty_trees, propagate_parent_transforms,
+        sync_simple_transforms,
+    },
+};
+
+use crate::as_world::AsWorld;
+
+/// runs the systems of `TransformPlugin` on demand
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct PropagateTransforms;
+
+#[doc = include_str!("./assert_hierarchy.md")]
+pub trait AssertHierarchy {
+    fn propagate_transforms(&mut self) -> &mut Self;
+    fn global_transform(&self, entity: Entity) -> Option<GlobalTransform>;
+    fn assert_global_transform(
+        &self,
+        entity: Entity,
+        expected: impl Into<GlobalTransform>,
+        epsilon: f32,
+    );
+    fn is_child_of(&self, child: Entity, parent: Entity) -> bool;
+    fn has_children_count(&self, entity: Entity, count: usize) -> bool;
+    fn descendants_matching(
+        &self,
+        entity: Entity,
+        predicate: impl Fn(EntityRef) -> bool,
+    ) -> Vec<Entity>;
+}
+
+impl<T: AsWorld> AssertHierarchy for T {
+    fn propagate_transforms(&mut self) -> &mut Self {
+        let world = self.as_world_mut();
+        if !world.contains_resource::<StaticTransformOptimizations>() {
+            world.init_resource::<StaticTransformOptimizations>();
+        }
+        let mut schedules = world.get_resource_or_init::<Schedules>();
+        if !schedules.contains(PropagateTransforms) {
+            schedules.add_systems(
+                PropagateTransforms,
+                (
+                    mark_dirty_trees,
+                    propagate_parent_transforms,
+                    sync_simple_transforms,
+                )
+                    .chain(),
+            );
+        }
+        world.run_schedule(PropagateTransforms);
+        self
+    }
+
+    fn global_transform(&self, entity: Entity) -> Option<GlobalTransform> {
+        self.as_world().get::<GlobalTransform>(entity).copied()
+    }
+
+    #[track_caller]
+    fn assert_global_transform(
+        &self,
+        entity: Entity,
+        expected: impl Into<GlobalTransform>,
+        epsilon: f32,
+    ) {
+        let expected = expected.into();
+        let Some(actual) = self.global_transform(entity) else {
+            panic!("expected {entity} to have a GlobalTransform");
+        };
+        if !actual.affine().abs_diff_eq(expected.affine(), epsilon) {
+            panic!(
+                "expected the GlobalTransform of {entity} to be within {epsilon} of\n  {:?}\nbut it was\n  {:?}",
+                expected.compute_transform(),
+                actual.compute_transform(),
+            );
+        }
+    }
+
+    fn is_child_of(&self, child: Entity, parent: Entity) -> bool {
+        self.as_world()
+            .get::<ChildOf>(child)
+            .is_some_and(|child_of| child_of.parent() == parent)
+    }
+
+    fn has_children_count(&self, entity: Entity, count: usize) -> bool {
+        self.as_world()
+            .get::<Children>(entity)
+            .map_or(0, |children| children.len())
+            == count
+    }
+
+    fn descendants_matching(
+        &self,
+        entity: Entity,
+        predicate: impl Fn(EntityRef) -> bool,
+    ) -> Vec<Entity> {
+        let world = self.as_world();
+        let mut matching = Vec::new();
+        // depth-first, in the order of `Children`
+        let mut stack: Vec<Entity> = Vec::new();
+        let children = |entity| {
+            world
+                .get::<Children>(entity)
+                .into_iter()
+                .flat_map(|children| children.iter().rev())
+        };
+        stack.extend(children(entity));
+        while let Some(descendant) = stack.pop() {
+            if world.get_entity(descendant).is_ok_and(&predicate) {
+                matching.push(descendant);
+            }
+            stack.extend(children(descendant));
+        }
+        matching
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_internal::{math::Vec3, transform::components::Transform};
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    #[rstest]
+    fn assert_hierarchy_trait_propagate_transforms(#[from(minimal_test_app)] mut app: TestApp) {
+        let parent = app
+            .world_mut()
+            .spawn(Transform::from_xyz(1.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)))
+            .id();
+        let child = app
+            .world_mut()
+            .spawn((Transform::from_xyz(0.0, 1.0, 0.0), ChildOf(parent)))
+            .id();
+
+        app.update();
+        assert_that!(app.global_transform(child))
+            .named("no TransformPlugin in minimal_test_app")
+            .is_equal_to(Some(GlobalTransform::IDENTITY));
+
+        app.propagate_transforms();
+
+        app.assert_global_transform(
+            child,
+            Transform::from_xyz(1.0, 2.0, 0.0).with_scale(Vec3::splat(2.0)),
+            1e-6,
+        );
+    }
+
+    #[rstest]
+    #[should_panic(expected = "to be within 0.001 of")]
+    fn assert_hierarchy_trait_global_transform_mismatch(
+        #[from(minimal_test_app)] mut app: TestApp,
+    ) {
+        let entity = app
+            .world_mut()
+            .spawn(Transform::from_xyz(1.0, 0.0, 0.0))
+            .id();
+
+        app.propagate_transforms().assert_global_transform(
+            entity,
+            Transform::from_xyz(1.01, 0.0, 0.0),
+            0.001,
+        );
+    }
+
+    #[rstest]
+    fn assert_hierarchy_trait_relations(#[from(minimal_test_app)] mut app: TestApp) {
+        let root = app.world_mut().spawn_empty().id();
+        let a = app
+            .world_mut()
+            .spawn((ChildOf(root), Transform::default()))
+            .id();
+        let b = app.world_mut().spawn(ChildOf(root)).id();
+        let c = app
+            .world_mut()
+            .spawn((ChildOf(a), Transform::default()))
+            .id();
+
+        assert_that!(app.is_child_of(a, root)).is_true();
+        assert_that!(app.is_child_of(c, root))
+            .named("grandchildren aren't children")
+            .is_false();
+        assert_that!(app.has_children_count(root, 2)).is_true();
+        assert_that!(app.has_children_count(b, 0)).is_true();
+        assert_that!(app.descendants_matching(root, |entity| entity.contains::<Transform>()))
+            .is_equal_to(vec![a, c]);
+    }
+}
@@ -0,0 +1,345 @@
+use std::{
+    any::TypeId,
+    collections::{BTreeSet, HashSet},
+    fmt::{self, Display, Formatter},
+};
+
+use bevy_ecs::{
+    component::ComponentInfo,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+};
+use bevy_internal::reflect::{
+    ReflectSerialize, TypeInfo, TypeRegistry, enums::VariantInfo, serde::SerializationData,
+};
+
+use crate::as_world::AsWorld;
+
+/// Type path prefixes of the crates skipped by default: bevy, `std`, and this
+/// crate, whose resources like `CapturedLogs` aren't meant to be saved.
+pub const SKIPPED_CRATES: [&str; 5] = [
+    "bevy_",
+    "core::",
+    "alloc::",
+    "std::",
+    concat!(env!("CARGO_CRATE_NAME"), "::"),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MissingReflection {
+    /// not in the `AppTypeRegistry` at all
+    Registration,
+    ReflectComponent,
+    ReflectResource,
+    /// a field, maybe nested, has this type that can't be serialized
+    Serialize {
+        field_type: &'static str,
+    },
+}
+
+/// A component or resource that can't be saved in a scene.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReflectionProblem {
+    pub type_path: String,
+    pub missing: MissingReflection,
+}
+
+impl Display for ReflectionProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let type_path = &self.type_path;
+        match &self.missing {
+            MissingReflection::Registration => {
+                write!(f, "`{type_path}` isn't registered, use `app.register_type`")
+            }
+            MissingReflection::ReflectComponent => {
+                write!(f, "`{type_path}` is missing `#[reflect(Component)]`")
+            }
+            MissingReflection::ReflectResource => {
+                write!(f, "`{type_path}` is missing `#[reflect(Resource)]`")
+            }
+            MissingReflection::Serialize { field_type } => write!(
+                f,
+                "`{type_path}` can't be serialized, `{field_type}` isn't registered or is missing `#[reflect(Serialize)]`"
+            ),
+        }
+    }
+}
+
+#[doc = include_str!("./audit_reflection.md")]
+pub trait AuditReflection {
+    /// the problems of the types not starting with one of `skipped`
+    fn reflection_problems_skipping(&self, skipped: &[&str]) -> Vec<ReflectionProblem>;
+    fn assert_reflection_registered_skipping(&self, skipped: &[&str]);
+
+    fn reflection_problems(&self) -> Vec<ReflectionProblem> {
+        self.reflection_problems_skipping(&SKIPPED_CRATES)
+    }
+
+    #[track_caller]
+    fn assert_reflection_registered(&self) {
+        self.assert_reflection_registered_skipping(&SKIPPED_CRATES);
+    }
+}
+
+impl<T: AsWorld> AuditReflection for T {
+    fn reflection_problems_skipping(&self, skipped: &[&str]) -> Vec<ReflectionProblem> {
+        let world = self.as_world();
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let resources = world
+            .iter_resources()
+            .map(|(info, _)| info)
+            .collect::<Vec<_>>();
+        // resources are stored on entities too
+        let components = world
+            .archetypes()
+            .iter()
+            .filter(|archetype| !archetype.is_empty())
+            .flat_map(|archetype| archetype.components().iter().copied())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|id| !resources.iter().any(|info| info.id() == *id))
+            .filter_map(|id| world.components().get_info(id))
+            .filter_map(|info| audit(&registry, info, false, skipped));
+        let resources = resources
+            .iter()
+            .filter_map(|info| audit(&registry, info, true, skipped));
+        let mut problems = components.chain(resources).collect::<Vec<_>>();
+        problems.sort_by(|a, b| a.type_path.cmp(&b.type_path));
+        problems
+    }
+
+    #[track_caller]
+    fn assert_reflection_registered_skipping(&self, skipped: &[&str]) {
+        let problems = self.reflection_problems_skipping(skipped);
+        if !problems.is_empty() {
+            panic!(
+                "{} type(s) can't be saved in a scene:\n  {}",
+                problems.len(),
+                problems
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n  ")
+            );
+        }
+    }
+}
+
+fn audit(
+    registry: &TypeRegistry,
+    info: &ComponentInfo,
+    resource: bool,
+    skipped: &[&str],
+) -> Option<ReflectionProblem> {
+    let registration = info.type_id().and_then(|type_id| registry.get(type_id));
+    let type_path = match registration {
+        Some(registration) => registration.type_info().type_path().to_string(),
+        // `trait_audit_reflection` enables bevy's `debug` feature for this name
+        None => info.name().to_string(),
+    };
+    if skipped.iter().any(|skipped| type_path.starts_with(skipped)) {
+        return None;
+    }
+    let problem = |missing| Some(ReflectionProblem { type_path, missing });
+    let Some(registration) = registration else {
+        return problem(MissingReflection::Registration);
+    };
+    if resource && registration.data::<ReflectResource>().is_none() {
+        return problem(MissingReflection::ReflectResource);
+    }
+    if !resource && registration.data::<ReflectComponent>().is_none() {
+        return problem(MissingReflection::ReflectComponent);
+    }
+    if let Some(field_type) = unserializable(registry, registration.type_id(), &mut HashSet::new())
+    {
+        return problem(MissingReflection::Serialize { field_type });
+    }
+    None
+}
+
+/// the first type in `type_id`, or itself, that the reflection serializer
+/// can't handle
+fn unserializable(
+    registry: &TypeRegistry,
+    type_id: TypeId,
+    visited: &mut HashSet<TypeId>,
+) -> Option<&'static str> {
+    if !visited.insert(type_id) {
+        return None;
+    }
+    let Some(registration) = registry.get(type_id) else {
+        // `TypeId`s don't have names, the caller knows it
+        return Some("");
+    };
+    if registration.data::<ReflectSerialize>().is_some() {
+        return None;
+    }
+    let skipped = |index| {
+        registration
+            .data::<SerializationData>()
+            .is_some_and(|data| data.is_field_skipped(index))
+    };
+    let mut check = |fields: Vec<(TypeId, &'static str)>| {
+        fields.into_iter().find_map(|(type_id, type_path)| {
+            unserializable(registry, type_id, visited).map(|field_type| {
+                if field_type.is_empty() {
+                    type_path
+                } else {
+                    field_type
+                }
+            })
+        })
+    };
+    match registration.type_info() {
+        TypeInfo::Struct(info) => check(
+            info.iter()
+                .enumerate()
+                .filter(|(index, _)| !skipped(*index))
+                .map(|(_, field)| (field.type_id(), field.type_path()))
+                .collect(),
+        ),
+        TypeInfo::TupleStruct(info) => check(
+            info.iter()
+                .enumerate()
+                .filter(|(index, _)| !skipped(*index))
+                .map(|(_, field)| (field.type_id(), field.type_path()))
+                .collect(),
+        ),
+        TypeInfo::Tuple(info) => check(
+            info.iter()
+                .map(|field| (field.type_id(), field.type_path()))
+                .collect(),
+        ),
+        TypeInfo::List(info) => check(vec![(info.item_ty().id(), info.item_ty().path())]),
+        TypeInfo::Array(info) => check(vec![(info.item_ty().id(), info.item_ty().path())]),
+        TypeInfo::Map(info) => check(vec![
+            (info.key_ty().id(), info.key_ty().path()),
+            (info.value_ty().id(), info.value_ty().path()),
+        ]),
+        TypeInfo::Set(info) => check(vec![(info.value_ty().id(), info.value_ty().path())]),
+        TypeInfo::Enum(info) => check(
+            info.iter()
+                .flat_map(|variant| match variant {
+                    VariantInfo::Struct(variant) => variant
+                        .iter()
+                        .map(|field| (field.type_id(), field.type_path()))
+                        .collect::<Vec<_>>(),
+                    VariantInfo::Tuple(variant) => variant
+                        .iter()
+                        .map(|field| (field.type_id(), field.type_path()))
+                        .collect(),
+                    VariantInfo::Unit(_) => Vec::new(),
+                })
+                .collect(),
+        ),
+        TypeInfo::Opaque(info) => Some(info.type_path()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::reflect::{Reflect, TypePath};
+    use bevy_ecs::{component::Component, resource::Resource};
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    /// the defaults would skip the types of these tests
+    const NOT_OWN_CRATE: [&str; 4] = ["bevy_", "core::", "alloc::", "std::"];
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Saved(u32);
+
+    #[derive(Component, Reflect, Default)]
+    struct NoReflectComponent;
+
+    #[derive(Component)]
+    struct NotRegistered;
+
+    /// opaque and not `#[reflect(Serialize)]`
+    #[derive(Reflect, Default, Clone)]
+    #[reflect(opaque)]
+    struct Secret;
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Settings {
+        volume: u8,
+        secrets: Vec<Secret>,
+    }
+
+    #[rstest]
+    fn audit_reflection_trait_nothing_missing(#[from(minimal_test_app)] mut app: TestApp) {
+        app.register_type::<Saved>();
+        app.world_mut().spawn(Saved(1));
+
+        app.assert_reflection_registered_skipping(&NOT_OWN_CRATE);
+    }
+
+    #[rstest]
+    fn audit_reflection_trait_problems(#[from(minimal_test_app)] mut app: TestApp) {
+        app.register_type::<Saved>()
+            .register_type::<NoReflectComponent>()
+            .register_type::<Settings>()
+            .init_resource::<Settings>();
+        app.world_mut()
+            .spawn((Saved(1), NoReflectComponent, NotRegistered));
+
+        let missing = app
+            .reflection_problems_skipping(&NOT_OWN_CRATE)
+            .into_iter()
+            .map(|problem| {
+                (
+                    problem.type_path.rsplit("::").next().unwrap().to_string(),
+                    problem.missing,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_that!(missing).is_equal_to(vec![
+            (
+                "NoReflectComponent".to_string(),
+                MissingReflection::ReflectComponent,
+            ),
+            ("NotRegistered".to_string(), MissingReflection::Registration),
+            (
+                "Settings".to_string(),
+                MissingReflection::Serialize {
+                    field_type: Secret::type_path(),
+                },
+            ),
+        ]);
+    }
+
+    #[rstest]
+    fn audit_reflection_trait_registered_type_path(#[from(minimal_test_app)] mut app: TestApp) {
+        app.register_type::<NoReflectComponent>();
+        app.world_mut().spawn(NoReflectComponent);
+
+        assert_that!(app.reflection_problems_skipping(&NOT_OWN_CRATE)).is_equal_to(vec![
+            ReflectionProblem {
+                type_path: NoReflectComponent::type_path().to_string(),
+                missing: MissingReflection::ReflectComponent,
+            },
+        ]);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "1 type(s) can't be saved in a scene")]
+    fn audit_reflection_trait_assert(#[from(minimal_test_app)] mut app: TestApp) {
+        app.world_mut().spawn(NotRegistered);
+
+        app.assert_reflection_registered_skipping(&NOT_OWN_CRATE);
+    }
+
+    #[rstest]
+    fn audit_reflection_trait_skips_own_crate_by_default(
+        #[from(minimal_test_app)] mut app: TestApp,
+    ) {
+        app.world_mut().spawn(NotRegistered);
+
+        assert_that!(app.reflection_problems()).is_empty();
+    }
+}
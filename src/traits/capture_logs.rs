@@ -0,0 +1,122 @@
+use tracing::Level;
+
+use crate::{
+    as_world::AsWorld,
+    logs::{CapturedLog, CapturedLogs, capture_logs_in},
+};
+
+#[doc = include_str!("./capture_logs.md")]
+pub trait CaptureLogs {
+    fn capture_logs(&mut self) -> &mut Self;
+    fn captured_logs(&self) -> Option<Vec<CapturedLog>>;
+}
+
+impl<T: AsWorld> CaptureLogs for T {
+    fn capture_logs(&mut self) -> &mut Self {
+        capture_logs_in(self.as_world_mut());
+        self
+    }
+
+    fn captured_logs(&self) -> Option<Vec<CapturedLog>> {
+        self.as_world()
+            .get_resource::<CapturedLogs>()
+            .map(CapturedLogs::get)
+    }
+}
+
+/// Asserts that a log with the given level was captured, optionally matching
+/// its message with `contains "text"` or `equals "text"`.
+#[macro_export]
+macro_rules! assert_logged {
+    ($app:expr, $level:expr) => {
+        $crate::prelude::assert_logged_with(&$app, $level, "any message", |_| true)
+    };
+    ($app:expr, $level:expr, contains $text:expr) => {
+        $crate::prelude::assert_logged_with(
+            &$app,
+            $level,
+            &format!("a message containing {:?}", $text),
+            |message| message.contains($text),
+        )
+    };
+    ($app:expr, $level:expr, equals $text:expr) => {
+        $crate::prelude::assert_logged_with(
+            &$app,
+            $level,
+            &format!("the message {:?}", $text),
+            |message| message == $text,
+        )
+    };
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_logged_with(
+    app: &impl CaptureLogs,
+    level: Level,
+    description: &str,
+    matches: impl Fn(&str) -> bool,
+) {
+    let logs = app
+        .captured_logs()
+        .expect("logs are not captured, call `capture_logs()` first");
+    if !logs
+        .iter()
+        .any(|log| log.level == level && matches(&log.message))
+    {
+        let captured = logs
+            .iter()
+            .map(|log| format!("  {log}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        panic!("expected a {level} log with {description}, captured:\n{captured}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::Update;
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    #[rstest]
+    fn capture_logs_trait_captured_logs(#[from(minimal_test_app)] mut app: TestApp) {
+        assert_that!(app.captured_logs())
+            .named("no plugin before capture_logs")
+            .is_none();
+
+        app.capture_logs();
+
+        assert_that!(app.captured_logs())
+            .named("plugin inserted after capture_logs")
+            .is_some()
+            .is_empty();
+    }
+
+    #[rstest]
+    fn capture_logs_trait_assert_logged(#[from(minimal_test_app)] mut app: TestApp) {
+        app.capture_logs().add_systems(Update, || {
+            tracing::warn!(slot = 2, "save failed: disk full")
+        });
+
+        app.update();
+
+        assert_logged!(app, Level::WARN);
+        assert_logged!(app, Level::WARN, contains "save failed");
+        assert_logged!(app, Level::WARN, equals "save failed: disk full");
+    }
+
+    #[rstest]
+    #[should_panic(expected = "expected a ERROR log")]
+    fn capture_logs_trait_assert_logged_level_mismatch(#[from(minimal_test_app)] mut app: TestApp) {
+        app.capture_logs()
+            .add_systems(Update, || tracing::warn!("save failed"));
+
+        app.update();
+
+        assert_logged!(app, Level::ERROR, contains "save failed");
+    }
+}
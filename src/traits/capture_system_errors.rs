@@ -0,0 +1,87 @@
+use crate::{
+    as_world::AsWorld,
+    system_errors::{CapturedSystemErrors, SystemError, capture_system_errors_in},
+};
+
+#[doc = include_str!("./capture_system_errors.md")]
+pub trait CaptureSystemErrors {
+    fn capture_system_errors(&mut self) -> &mut Self;
+    fn system_errors(&self) -> Option<Vec<SystemError>>;
+    fn acknowledge_system_errors(&self) -> Vec<SystemError>;
+}
+
+impl<T: AsWorld> CaptureSystemErrors for T {
+    fn capture_system_errors(&mut self) -> &mut Self {
+        capture_system_errors_in(self.as_world_mut());
+        self
+    }
+
+    fn system_errors(&self) -> Option<Vec<SystemError>> {
+        self.as_world()
+            .get_resource::<CapturedSystemErrors>()
+            .map(CapturedSystemErrors::get)
+    }
+
+    fn acknowledge_system_errors(&self) -> Vec<SystemError> {
+        self.as_world()
+            .get_resource::<CapturedSystemErrors>()
+            .map(CapturedSystemErrors::acknowledge)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::Update;
+    use bevy_ecs::{error::Result, system::Commands};
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    #[rstest]
+    fn capture_system_errors_trait_system_errors(#[from(minimal_test_app)] mut app: TestApp) {
+        assert_that!(app.system_errors())
+            .named("no plugin before capture_system_errors")
+            .is_none();
+
+        app.capture_system_errors();
+
+        assert_that!(app.system_errors())
+            .named("plugin inserted after capture_system_errors")
+            .is_some()
+            .is_empty();
+    }
+
+    #[rstest]
+    fn capture_system_errors_trait_acknowledge(#[from(minimal_test_app)] mut app: TestApp) {
+        app.capture_system_errors()
+            .add_systems(Update, || -> Result { Err("boom".into()) });
+
+        app.update();
+
+        assert_that!(app.acknowledge_system_errors())
+            .named("first acknowledgement")
+            .has_length(1);
+        assert_that!(app.acknowledge_system_errors())
+            .named("second acknowledgement")
+            .is_empty();
+        assert_that!(app.system_errors())
+            .named("acknowledged errors are kept")
+            .is_some()
+            .has_length(1);
+    }
+
+    #[rstest]
+    fn capture_system_errors_trait_failed_command(#[from(minimal_test_app)] mut app: TestApp) {
+        app.capture_system_errors()
+            .add_systems(Update, |mut commands: Commands| {
+                commands.queue(|_: &mut bevy_ecs::world::World| -> Result { Err("boom".into()) });
+            });
+
+        app.update();
+
+        assert_that!(app.acknowledge_system_errors()).has_length(1);
+    }
+}
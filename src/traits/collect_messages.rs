@@ -1,8 +1,8 @@
 use bevy_ecs::message::Message;
 
 use crate::{
-    messages::{CollectedMessages, MessageCollectorPlugin, MessageFilterPlugin},
-    prelude::TestApp,
+    as_world::AsWorld,
+    messages::{CollectedMessages, collect_messages_in},
 };
 
 #[doc = include_str!("./collect_messages.md")]
@@ -13,24 +13,25 @@ pub trait CollectMessages {
     fn get_collected_messages<E: Message + Clone>(&self) -> Option<Vec<E>>;
 }
 
-impl CollectMessages for TestApp {
-    fn collect_messages<E: Message + Clone>(&mut self) -> &mut TestApp {
-        self.add_plugins(MessageCollectorPlugin::<E>::default());
+impl<T: AsWorld> CollectMessages for T {
+    fn collect_messages<E: Message + Clone>(&mut self) -> &mut Self {
+        collect_messages_in::<E>(self.as_world_mut(), |_| true);
         self
     }
 
-    fn collect_messages_only<E: Message + Clone + PartialEq>(&mut self, message: E) -> &mut TestApp {
-        self.add_plugins(MessageFilterPlugin::<E>::Only(message.clone()));
+    fn collect_messages_only<E: Message + Clone + PartialEq>(&mut self, message: E) -> &mut Self {
+        collect_messages_in(self.as_world_mut(), move |ev: &E| *ev == message);
         self
     }
 
     fn collect_messages_any_of<E: Message + Clone + PartialEq>(&mut self, messages: &[E]) -> &mut Self {
-        self.add_plugins(MessageFilterPlugin::<E>::AnyOf(messages.into()));
+        let messages = messages.to_vec();
+        collect_messages_in(self.as_world_mut(), move |ev: &E| messages.contains(ev));
         self
     }
 
     fn get_collected_messages<E: Message + Clone>(&self) -> Option<Vec<E>> {
-        self.world()
+        self.as_world()
             .get_resource::<CollectedMessages<E>>()
             .map(|e| e.get().clone())
     }
@@ -100,4 +101,28 @@ mod tests {
             .named("plugin inserted after collect_messages_any_of")
             .is_some();
     }
+
+    #[rstest]
+    fn message_collector_trait_on_app_and_world() {
+        use bevy_app::{App, PostUpdate};
+        use bevy_ecs::world::World;
+
+        use crate::traits::WriteMessages;
+
+        let mut app = App::new();
+        app.collect_messages::<MyMessage>();
+        app.write_message(MyMessage);
+        app.update();
+        assert_that!(app.get_collected_messages::<MyMessage>())
+            .named("collected in App")
+            .is_equal_to(Some(vec![MyMessage]));
+
+        let mut world = World::new();
+        world.collect_messages::<MyMessage>();
+        WriteMessages::write_message(&mut world, MyMessage);
+        world.run_schedule(PostUpdate);
+        assert_that!(world.get_collected_messages::<MyMessage>())
+            .named("collected in World")
+            .is_equal_to(Some(vec![MyMessage]));
+    }
 }
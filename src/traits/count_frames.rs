@@ -0,0 +1,86 @@
+use bevy_app::Last;
+use bevy_ecs::{change_detection::Tick, schedule::Schedules};
+use bevy_internal::diagnostic::{FrameCount, update_frame_count};
+
+use crate::as_world::AsWorld;
+
+#[doc = include_str!("./count_frames.md")]
+pub trait CountFrames {
+    fn count_frames(&mut self) -> &mut Self;
+    fn frame(&self) -> u32;
+    fn change_tick(&self) -> Tick;
+}
+
+impl<T: AsWorld> CountFrames for T {
+    fn count_frames(&mut self) -> &mut Self {
+        let world = self.as_world_mut();
+        // what `FrameCountPlugin` does, which needs an `App`
+        if !world.contains_resource::<FrameCount>() {
+            world.init_resource::<FrameCount>();
+            world
+                .get_resource_or_init::<Schedules>()
+                .add_systems(Last, update_frame_count);
+        }
+        self
+    }
+
+    #[track_caller]
+    fn frame(&self) -> u32 {
+        self.as_world()
+            .get_resource::<FrameCount>()
+            .expect("frames aren't counted, call `count_frames()` first")
+            .0
+    }
+
+    fn change_tick(&self) -> Tick {
+        self.as_world().read_change_tick()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    #[rstest]
+    fn count_frames_trait_frame(#[from(minimal_test_app)] mut app: TestApp) {
+        assert_that!(app.frame())
+            .named("FrameCountPlugin is part of MinimalPlugins")
+            .is_equal_to(0);
+
+        app.count_frames().after_frames(3, |app| app.frame() == 3);
+
+        assert_that!(app.frame())
+            .named("counted once per frame")
+            .is_equal_to(3);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "call `count_frames()` first")]
+    fn count_frames_trait_frame_without_counter() {
+        App::new().frame();
+    }
+
+    #[rstest]
+    fn count_frames_trait_without_plugin() {
+        let mut app = App::new();
+
+        app.count_frames();
+        app.update();
+
+        assert_that!(app.frame()).is_equal_to(1);
+    }
+
+    #[rstest]
+    fn count_frames_trait_change_tick(#[from(minimal_test_app)] mut app: TestApp) {
+        let before = app.change_tick();
+
+        app.update();
+
+        assert_that!(app.change_tick().get()).is_greater_than(before.get());
+    }
+}
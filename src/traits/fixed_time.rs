@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use bevy_app::{First, FixedFirst, FixedMain};
+use bevy_ecs::{
+    resource::Resource,
+    schedule::{IntoScheduleConfigs, Schedules},
+    system::{Res, ResMut},
+};
+use bevy_internal::time::{Fixed, Time, TimeSystems, TimeUpdateStrategy, Virtual};
+
+use crate::{
+    as_world::AsWorld,
+    test_app::{allow_virtual_delta, start_real_time},
+};
+
+/// Number of [`FixedMain`] runs since the start of the last update, or of
+/// the last [`FixedTime::run_fixed_steps`].
+#[derive(Clone, Copy, Debug, Default, Resource)]
+pub struct FixedSteps(pub u32);
+
+/// The steps of [`FixedTime::set_fixed_steps_per_update`], the delta they
+/// need depends on the timestep at the time of the update.
+#[derive(Clone, Copy, Debug, Resource)]
+struct FixedStepsPerUpdate(u32);
+
+/// a clamped delta would skip steps
+fn allow_fixed_steps(
+    steps: Res<FixedStepsPerUpdate>,
+    fixed_time: Res<Time<Fixed>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    allow_virtual_delta(&mut virtual_time, fixed_time.timestep() * steps.0);
+}
+
+#[doc = include_str!("./fixed_time.md")]
+pub trait FixedTime {
+    fn set_fixed_timestep(&mut self, timestep: Duration) -> &mut Self;
+    fn set_fixed_steps_per_update(&mut self, steps: u32) -> &mut Self;
+    fn run_fixed_steps(&mut self, steps: u32) -> &mut Self;
+    fn count_fixed_steps(&mut self) -> &mut Self;
+    fn fixed_steps_last_update(&self) -> Option<u32>;
+}
+
+impl<T: AsWorld> FixedTime for T {
+    fn set_fixed_timestep(&mut self, timestep: Duration) -> &mut Self {
+        self.as_world_mut()
+            .resource_mut::<Time<Fixed>>()
+            .set_timestep(timestep);
+        self
+    }
+
+    fn set_fixed_steps_per_update(&mut self, steps: u32) -> &mut Self {
+        let world = self.as_world_mut();
+        start_real_time(world);
+        if !world.contains_resource::<FixedStepsPerUpdate>() {
+            world
+                .get_resource_or_init::<Schedules>()
+                .add_systems(First, allow_fixed_steps.before(TimeSystems));
+        }
+        world.insert_resource(FixedStepsPerUpdate(steps));
+        world.insert_resource(TimeUpdateStrategy::FixedTimesteps(steps));
+        self
+    }
+
+    fn run_fixed_steps(&mut self, steps: u32) -> &mut Self {
+        let world = self.as_world_mut();
+        // counted like an update of their own
+        if let Some(mut fixed_steps) = world.get_resource_mut::<FixedSteps>() {
+            fixed_steps.0 = 0;
+        }
+        world.schedule_scope(FixedMain, |world, schedule| {
+            for _ in 0..steps {
+                // like `run_fixed_main_schedule`, without touching the overstep
+                let mut fixed = world.resource_mut::<Time<Fixed>>();
+                let timestep = fixed.timestep();
+                fixed.advance_by(timestep);
+                *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+                schedule.run(world);
+            }
+        });
+        *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+        self
+    }
+
+    fn count_fixed_steps(&mut self) -> &mut Self {
+        let world = self.as_world_mut();
+        if !world.contains_resource::<FixedSteps>() {
+            world.init_resource::<FixedSteps>();
+            let mut schedules = world.get_resource_or_init::<Schedules>();
+            schedules.add_systems(First, |mut steps: ResMut<FixedSteps>| steps.0 = 0);
+            schedules.add_systems(FixedFirst, |mut steps: ResMut<FixedSteps>| steps.0 += 1);
+        }
+        self
+    }
+
+    fn fixed_steps_last_update(&self) -> Option<u32> {
+        self.as_world()
+            .get_resource::<FixedSteps>()
+            .map(|steps| steps.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::FixedUpdate;
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    #[rstest]
+    fn fixed_time_trait_fixed_steps_per_update(#[from(minimal_test_app)] mut app: TestApp) {
+        assert_that!(app.fixed_steps_last_update())
+            .named("not counted before count_fixed_steps")
+            .is_none();
+
+        app.count_fixed_steps()
+            .set_fixed_timestep(Duration::from_millis(100))
+            .set_fixed_steps_per_update(3);
+
+        app.update();
+        assert_that!(app.fixed_steps_last_update())
+            .named("first update")
+            .is_equal_to(Some(3));
+
+        app.update();
+        assert_that!(app.fixed_steps_last_update())
+            .named("second update")
+            .is_equal_to(Some(3));
+    }
+
+    #[rstest]
+    fn fixed_time_trait_large_steps_per_update(#[from(minimal_test_app)] mut app: TestApp) {
+        app.count_fixed_steps()
+            .set_fixed_timestep(Duration::from_secs(1))
+            .set_fixed_steps_per_update(10);
+
+        app.update();
+
+        assert_that!(app.fixed_steps_last_update()).is_equal_to(Some(10));
+    }
+
+    #[rstest]
+    fn fixed_time_trait_timestep_after_steps_per_update(
+        #[from(minimal_test_app)] mut app: TestApp,
+    ) {
+        app.count_fixed_steps()
+            .set_fixed_steps_per_update(3)
+            .set_fixed_timestep(Duration::from_millis(200));
+
+        app.update();
+
+        assert_that!(app.fixed_steps_last_update()).is_equal_to(Some(3));
+    }
+
+    #[rstest]
+    fn fixed_time_trait_run_fixed_steps(#[from(minimal_test_app)] mut app: TestApp) {
+        #[derive(Default, Resource)]
+        struct Elapsed(Vec<Duration>);
+
+        app.init_resource::<Elapsed>().add_systems(
+            FixedUpdate,
+            |time: Res<Time>, mut elapsed: ResMut<Elapsed>| elapsed.0.push(time.elapsed()),
+        );
+        app.set_fixed_timestep(Duration::from_millis(10))
+            .count_fixed_steps()
+            .run_fixed_steps(3);
+
+        assert_that!(app.world().resource::<Elapsed>().0).is_equal_to(vec![
+            Duration::from_millis(10),
+            Duration::from_millis(20),
+            Duration::from_millis(30),
+        ]);
+        assert_that!(app.fixed_steps_last_update()).is_equal_to(Some(3));
+        assert_that!(app.world().resource::<Time>().elapsed())
+            .named("generic time is restored")
+            .is_equal_to(Duration::ZERO);
+    }
+
+    #[rstest]
+    fn fixed_time_trait_run_fixed_steps_after_update(#[from(minimal_test_app)] mut app: TestApp) {
+        app.count_fixed_steps()
+            .set_fixed_timestep(Duration::from_millis(10))
+            .set_fixed_steps_per_update(2);
+        app.update();
+
+        app.run_fixed_steps(3);
+
+        assert_that!(app.fixed_steps_last_update())
+            .named("steps of the update are not added")
+            .is_equal_to(Some(3));
+    }
+}
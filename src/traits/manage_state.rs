@@ -1,6 +1,6 @@
 use bevy_state::state::{FreelyMutableState, NextState, State, States};
 
-use crate::prelude::TestApp;
+use crate::as_world::AsWorld;
 
 #[doc = include_str!("./manage_state.md")]
 pub trait ManageState {
@@ -9,15 +9,15 @@ pub trait ManageState {
     fn set_next_state<S: FreelyMutableState>(&mut self, next: S) -> Option<()>;
 }
 
-impl ManageState for TestApp {
+impl<T: AsWorld> ManageState for T {
     fn get_state<S: States>(&self) -> Option<&S> {
-        self.world().get_resource::<State<S>>().map(|s| s.get())
+        self.as_world().get_resource::<State<S>>().map(|s| s.get())
     }
     fn get_next_state<S: FreelyMutableState>(&self) -> Option<&NextState<S>> {
-        self.world().get_resource::<NextState<S>>()
+        self.as_world().get_resource::<NextState<S>>()
     }
     fn set_next_state<S: FreelyMutableState>(&mut self, next: S) -> Option<()> {
-        self.world_mut()
+        self.as_world_mut()
             .get_resource_mut::<NextState<S>>()
             .map(|mut s| s.set(next))
     }
@@ -0,0 +1,108 @@
+use bevy_ecs::{component::Component, world::World};
+use bevy_render::RenderApp;
+
+use crate::as_world::AsApp;
+
+const NO_RENDER_WORLD: &str =
+    "no render world, add the `ExtractionPlugin` or use `headless_render_test_app`";
+
+#[doc = include_str!("./render_world.md")]
+pub trait RenderWorld {
+    /// Runs the extraction once, without updating the main world.
+    fn run_extract(&mut self) -> &mut Self;
+    fn render_world(&self) -> &World;
+    fn render_world_mut(&mut self) -> &mut World;
+
+    #[track_caller]
+    fn extracted<C: Component>(&self) -> Vec<&C> {
+        let world = self.render_world();
+        world
+            .try_query::<&C>()
+            .map(|mut query| query.iter(world).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl<T: AsApp> RenderWorld for T {
+    #[track_caller]
+    fn run_extract(&mut self) -> &mut Self {
+        let app = self.as_app_mut();
+        let mut render_app = app.remove_sub_app(RenderApp).expect(NO_RENDER_WORLD);
+        render_app.extract(app.world_mut());
+        app.insert_sub_app(RenderApp, render_app);
+        self
+    }
+
+    #[track_caller]
+    fn render_world(&self) -> &World {
+        self.as_app()
+            .get_sub_app(RenderApp)
+            .expect(NO_RENDER_WORLD)
+            .world()
+    }
+
+    #[track_caller]
+    fn render_world_mut(&mut self) -> &mut World {
+        self.as_app_mut()
+            .get_sub_app_mut(RenderApp)
+            .expect(NO_RENDER_WORLD)
+            .world_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        component::Component,
+        system::{Commands, Query},
+    };
+    use bevy_render::{Extract, ExtractSchedule, sync_world::TemporaryRenderEntity};
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{
+        fixtures::{headless_render_test_app, minimal_test_app},
+        test_app::TestApp,
+    };
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct ExtractedHealth(u32);
+
+    fn extract_health(mut commands: Commands, healths: Extract<Query<&Health>>) {
+        for health in &healths {
+            commands.spawn((TemporaryRenderEntity, ExtractedHealth(health.0)));
+        }
+    }
+
+    #[rstest]
+    fn render_world_trait_run_extract(#[from(headless_render_test_app)] mut app: TestApp) {
+        app.sub_app_mut(RenderApp)
+            .add_systems(ExtractSchedule, extract_health);
+        app.world_mut().spawn(Health(3));
+
+        assert_that!(app.extracted::<ExtractedHealth>()).is_empty();
+        assert_that!(app.run_extract().extracted::<ExtractedHealth>())
+            .is_equal_to(vec![&ExtractedHealth(3)]);
+        app.update();
+        assert_that!(app.extracted::<ExtractedHealth>())
+            .named("extracted again, without the previous frame's")
+            .is_equal_to(vec![&ExtractedHealth(3)]);
+    }
+
+    #[rstest]
+    fn render_world_trait_on_app(#[from(headless_render_test_app)] mut app: TestApp) {
+        app.render_world_mut().spawn(ExtractedHealth(1));
+
+        assert_that!(app.0.extracted::<ExtractedHealth>()).is_equal_to(vec![&ExtractedHealth(1)]);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "no render world")]
+    fn render_world_trait_without_render_world(#[from(minimal_test_app)] mut app: TestApp) {
+        app.run_extract();
+    }
+}
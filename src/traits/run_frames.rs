@@ -0,0 +1,174 @@
+use std::{hash::Hash, time::Duration};
+
+use bevy_app::PreUpdate;
+use bevy_ecs::{
+    message::Message,
+    resource::Resource,
+    schedule::{IntoScheduleConfigs, Schedules},
+    system::{Res, ResMut},
+};
+use bevy_input::{ButtonInput, InputSystems};
+
+use crate::{as_world::AsApp, test_app::update_with_delta, traits::WriteMessages};
+
+#[doc = include_str!("./run_frames.md")]
+pub trait RunFrames {
+    /// Writes each frame's messages, then updates.
+    fn run_message_frames<M: Message>(
+        &mut self,
+        frames: impl IntoIterator<Item = impl IntoIterator<Item = M>>,
+    ) -> &mut Self;
+    /// Holds each frame's buttons in [`ButtonInput<T>`], then updates.
+    ///
+    /// The buttons are pressed after [`InputSystems`], so it works with or
+    /// without `InputPlugin`.
+    fn run_input_frames<T: Copy + Eq + Hash + Send + Sync + 'static>(
+        &mut self,
+        frames: impl IntoIterator<Item = impl IntoIterator<Item = T>>,
+    ) -> &mut Self;
+    /// Updates once per delta, as if that much time passed since the last
+    /// frame.
+    fn run_frame_times(&mut self, deltas: impl IntoIterator<Item = Duration>) -> &mut Self;
+}
+
+impl<T: AsApp> RunFrames for T {
+    fn run_message_frames<M: Message>(
+        &mut self,
+        frames: impl IntoIterator<Item = impl IntoIterator<Item = M>>,
+    ) -> &mut Self {
+        let app = self.as_app_mut();
+        for messages in frames {
+            // recorded by a scenario like any other written message
+            WriteMessages::write_message_batch(app.world_mut(), messages);
+            app.update();
+        }
+        self
+    }
+
+    fn run_input_frames<B: Copy + Eq + Hash + Send + Sync + 'static>(
+        &mut self,
+        frames: impl IntoIterator<Item = impl IntoIterator<Item = B>>,
+    ) -> &mut Self {
+        let app = self.as_app_mut();
+        let world = app.world_mut();
+        if !world.contains_resource::<HeldInputs<B>>() {
+            world.init_resource::<ButtonInput<B>>();
+            world.insert_resource(HeldInputs::<B>(Vec::new()));
+            world
+                .get_resource_or_init::<Schedules>()
+                .add_systems(PreUpdate, hold_inputs::<B>.after(InputSystems));
+        }
+        for held in frames {
+            app.world_mut().resource_mut::<HeldInputs<B>>().0 = held.into_iter().collect();
+            app.update();
+        }
+        self
+    }
+
+    fn run_frame_times(&mut self, deltas: impl IntoIterator<Item = Duration>) -> &mut Self {
+        let sub_apps = self.as_app_mut().sub_apps_mut();
+        for delta in deltas {
+            update_with_delta(sub_apps, delta);
+        }
+        self
+    }
+}
+
+/// the buttons to hold during the next frame
+#[derive(Resource)]
+struct HeldInputs<T>(Vec<T>);
+
+fn hold_inputs<T: Copy + Eq + Hash + Send + Sync + 'static>(
+    held: Res<HeldInputs<T>>,
+    mut input: ResMut<ButtonInput<T>>,
+) {
+    // without `InputPlugin` nothing else clears them
+    input.clear();
+    let released = input
+        .get_pressed()
+        .filter(|button| !held.0.contains(button))
+        .copied()
+        .collect::<Vec<_>>();
+    for button in released {
+        input.release(button);
+    }
+    for button in &held.0 {
+        input.press(*button);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::Update;
+    use bevy_ecs::message::MessageReader;
+    use bevy_input::keyboard::KeyCode;
+    use bevy_internal::time::{Time, Virtual};
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    #[derive(Message)]
+    struct Scored(u32);
+
+    #[derive(Resource, Default)]
+    struct Score(u32);
+
+    #[rstest]
+    fn run_frames_trait_run_message_frames(#[from(minimal_test_app)] mut app: TestApp) {
+        app.add_message::<Scored>()
+            .init_resource::<Score>()
+            .add_systems(
+                Update,
+                |mut scored: MessageReader<Scored>, mut score: ResMut<Score>| {
+                    score.0 += scored.read().map(|scored| scored.0).sum::<u32>();
+                },
+            );
+
+        app.run_message_frames([vec![Scored(1), Scored(2)], vec![], vec![Scored(4)]]);
+
+        assert_that!(app.world().resource::<Score>().0).is_equal_to(7);
+    }
+
+    #[rstest]
+    fn run_frames_trait_run_input_frames(#[from(minimal_test_app)] mut app: TestApp) {
+        #[derive(Resource, Default)]
+        struct Jumps(u32);
+
+        app.init_resource::<Jumps>().add_systems(
+            Update,
+            |input: Res<ButtonInput<KeyCode>>, mut jumps: ResMut<Jumps>| {
+                if input.just_pressed(KeyCode::Space) {
+                    jumps.0 += 1;
+                }
+            },
+        );
+
+        app.run_input_frames([
+            vec![KeyCode::Space],
+            vec![KeyCode::Space, KeyCode::KeyA],
+            vec![],
+            vec![KeyCode::Space],
+        ]);
+
+        assert_that!(app.world().resource::<Jumps>().0).is_equal_to(2);
+        assert_that!(
+            app.world()
+                .resource::<ButtonInput<KeyCode>>()
+                .pressed(KeyCode::KeyA)
+        )
+        .is_false();
+    }
+
+    #[rstest]
+    fn run_frames_trait_run_frame_times(#[from(minimal_test_app)] mut app: TestApp) {
+        let deltas = [16, 500, 33].map(Duration::from_millis);
+
+        app.run_frame_times(deltas);
+
+        let time = app.world().resource::<Time<Virtual>>();
+        assert_that!(time.elapsed()).is_equal_to(Duration::from_millis(549));
+        assert_that!(time.delta()).is_equal_to(Duration::from_millis(33));
+    }
+}
@@ -0,0 +1,469 @@
+use std::{
+    any::TypeId,
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use bevy_asset::{AssetPath, AssetServer, LoadFromPath, UntypedHandle};
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    hierarchy::Children,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    world::World,
+};
+use bevy_internal::reflect::{PartialReflect, ReflectRef};
+use bevy_utils::prelude::ShortName;
+use bevy_world_serialization::{
+    DynamicEntity, DynamicWorld, DynamicWorldBuilder, WorldInstanceSpawnError,
+    serde::WorldDeserializer,
+};
+use serde::de::DeserializeSeed;
+
+use crate::as_world::AsWorld;
+
+#[derive(Debug)]
+pub enum SceneRoundTripError {
+    Serialize(ron::Error),
+    Deserialize(ron::error::SpannedError),
+    Spawn(WorldInstanceSpawnError),
+}
+
+impl Display for SceneRoundTripError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Serialize(error) => write!(f, "failed to serialize the scene: {error}"),
+            Self::Deserialize(error) => write!(f, "failed to deserialize the scene: {error}"),
+            Self::Spawn(error) => write!(f, "failed to spawn the scene: {error}"),
+        }
+    }
+}
+
+impl Error for SceneRoundTripError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Serialize(error) => Some(error),
+            Self::Deserialize(error) => Some(error),
+            Self::Spawn(error) => Some(error),
+        }
+    }
+}
+
+fn extract_scene(world: &World, entities: impl IntoIterator<Item = Entity>) -> DynamicWorld {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    DynamicWorldBuilder::from_world(world, &registry)
+        .extract_entities(entities.into_iter())
+        .build()
+}
+
+/// stands in for the `AssetServer` of apps without `AssetPlugin`
+struct NoAssetServer;
+
+impl LoadFromPath for NoAssetServer {
+    fn load_from_path_erased(&mut self, _: TypeId, path: AssetPath<'static>) -> UntypedHandle {
+        panic!("the scene loads `{path}`, but there is no `AssetServer`, add the `AssetPlugin`");
+    }
+}
+
+#[doc = include_str!("./scene_round_trip.md")]
+pub trait SceneRoundTrip {
+    fn to_scene_ron(
+        &self,
+        entities: impl IntoIterator<Item = Entity>,
+    ) -> Result<String, SceneRoundTripError>;
+    fn load_scene_ron(&mut self, ron: &str) -> Result<EntityHashMap<Entity>, SceneRoundTripError>;
+    fn assert_scene_round_trip(
+        &mut self,
+        entities: impl IntoIterator<Item = Entity>,
+    ) -> EntityHashMap<Entity>;
+}
+
+impl<T: AsWorld> SceneRoundTrip for T {
+    fn to_scene_ron(
+        &self,
+        entities: impl IntoIterator<Item = Entity>,
+    ) -> Result<String, SceneRoundTripError> {
+        let world = self.as_world();
+        let registry = world.resource::<AppTypeRegistry>().read();
+        extract_scene(world, entities)
+            .serialize(&registry)
+            .map_err(SceneRoundTripError::Serialize)
+    }
+
+    fn load_scene_ron(&mut self, ron: &str) -> Result<EntityHashMap<Entity>, SceneRoundTripError> {
+        let world = self.as_world_mut();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let mut deserializer =
+            ron::de::Deserializer::from_str(ron).map_err(SceneRoundTripError::Deserialize)?;
+        let mut asset_server = world.get_resource::<AssetServer>().cloned();
+        let load_from_path: &mut dyn LoadFromPath = match &mut asset_server {
+            Some(asset_server) => asset_server,
+            None => &mut NoAssetServer,
+        };
+        let scene = WorldDeserializer {
+            type_registry: &registry.read(),
+            load_from_path,
+        }
+        .deserialize(&mut deserializer)
+        .map_err(|error| SceneRoundTripError::Deserialize(deserializer.span_error(error)))?;
+        let mut entities = EntityHashMap::default();
+        scene
+            .write_to_world_with(world, &mut entities, &registry.read())
+            .map_err(SceneRoundTripError::Spawn)?;
+        Ok(entities)
+    }
+
+    #[track_caller]
+    fn assert_scene_round_trip(
+        &mut self,
+        entities: impl IntoIterator<Item = Entity>,
+    ) -> EntityHashMap<Entity> {
+        let entities = entities.into_iter().collect::<Vec<_>>();
+        let mut problems = unsaved_components(self.as_world(), &entities);
+        problems.extend(unsaved_children(self.as_world(), &entities));
+        let before = extract_scene(self.as_world(), entities.iter().copied());
+        let ron = match self.to_scene_ron(entities.iter().copied()) {
+            Ok(ron) => ron,
+            Err(error) => panic!("{error}"),
+        };
+
+        let world = self.as_world_mut();
+        for &entity in &entities {
+            // children went with their parent already
+            if world.get_entity(entity).is_ok() {
+                world.despawn(entity);
+            }
+        }
+        let mapping = match self.load_scene_ron(&ron) {
+            Ok(mapping) => mapping,
+            Err(error) => panic!("{error}\n{ron}"),
+        };
+
+        let after = extract_scene(
+            self.as_world(),
+            entities
+                .iter()
+                .filter_map(|entity| mapping.get(entity).copied()),
+        );
+        for before in &before.entities {
+            let new_entity = mapping[&before.entity];
+            let Some(after) = after
+                .entities
+                .iter()
+                .find(|after| after.entity == new_entity)
+            else {
+                problems.push(format!("{}: wasn't loaded back", before.entity));
+                continue;
+            };
+            compare_entities(before, after, &mapping, &mut problems);
+        }
+        if !problems.is_empty() {
+            panic!(
+                "the scene didn't survive a round trip:\n  {}\n{ron}",
+                problems.join("\n  ")
+            );
+        }
+        mapping
+    }
+}
+
+/// components a [`DynamicWorldBuilder`] skips
+fn unsaved_components(world: &World, entities: &[Entity]) -> Vec<String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut problems = Vec::new();
+    for &entity in entities {
+        let Ok(entity_ref) = world.get_entity(entity) else {
+            problems.push(format!("{entity}: doesn't exist"));
+            continue;
+        };
+        for &id in entity_ref.archetype().components() {
+            let Some(info) = world.components().get_info(id) else {
+                continue;
+            };
+            let registration = info.type_id().and_then(|type_id| registry.get(type_id));
+            if registration.is_none_or(|registration| registration.data::<ReflectComponent>().is_none()) {
+                // `trait_scene_round_trip` enables bevy's `debug` feature for
+                // the names of unregistered types
+                let name = match registration {
+                    Some(registration) => ShortName(registration.type_info().type_path()).to_string(),
+                    None => info.name().shortname().to_string(),
+                };
+                problems.push(format!(
+                    "{entity}: `{name}` wasn't saved, it's not registered with `#[reflect(Component)]`",
+                ));
+            }
+        }
+    }
+    problems
+}
+
+/// descendants that would be despawned along with `entities` without being
+/// saved
+fn unsaved_children(world: &World, entities: &[Entity]) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut parents = entities.to_vec();
+    while let Some(parent) = parents.pop() {
+        let Some(children) = world.get::<Children>(parent) else {
+            continue;
+        };
+        for &child in children.iter() {
+            if !entities.contains(&child) {
+                problems.push(format!(
+                    "{child}: wasn't saved, but despawned with its parent {parent}"
+                ));
+                parents.push(child);
+            }
+        }
+    }
+    problems
+}
+
+fn compare_entities(
+    before: &DynamicEntity,
+    after: &DynamicEntity,
+    mapping: &EntityHashMap<Entity>,
+    problems: &mut Vec<String>,
+) {
+    for component in &before.components {
+        let type_path = component.reflect_type_path();
+        let path = format!("{}: {}", before.entity, ShortName(type_path));
+        match after
+            .components
+            .iter()
+            .find(|after| after.reflect_type_path() == type_path)
+        {
+            Some(loaded) => compare_values(
+                &path,
+                component.as_ref(),
+                loaded.as_ref(),
+                mapping,
+                problems,
+            ),
+            None => problems.push(format!("{path} wasn't loaded back")),
+        }
+    }
+}
+
+fn compare_values(
+    path: &str,
+    before: &dyn PartialReflect,
+    after: &dyn PartialReflect,
+    mapping: &EntityHashMap<Entity>,
+    problems: &mut Vec<String>,
+) {
+    // entities are remapped on load
+    if let (Some(before), Some(after)) = (
+        before.try_downcast_ref::<Entity>(),
+        after.try_downcast_ref::<Entity>(),
+    ) {
+        if mapping.get(before).unwrap_or(before) != after {
+            problems.push(format!("{path} changed from {before} to {after}"));
+        }
+        return;
+    }
+    match (before.reflect_ref(), after.reflect_ref()) {
+        (ReflectRef::Struct(before), ReflectRef::Struct(after)) => compare_fields(
+            path,
+            mapping,
+            problems,
+            (0..before.field_len())
+                .map(|index| {
+                    let name = before.name_at(index).unwrap_or_default();
+                    (
+                        format!(".{name}"),
+                        before.field_at(index),
+                        after.field(name),
+                    )
+                })
+                .collect(),
+        ),
+        (ReflectRef::TupleStruct(before), ReflectRef::TupleStruct(after)) => compare_fields(
+            path,
+            mapping,
+            problems,
+            (0..before.field_len().max(after.field_len()))
+                .map(|index| (format!(".{index}"), before.field(index), after.field(index)))
+                .collect(),
+        ),
+        (ReflectRef::Tuple(before), ReflectRef::Tuple(after)) => compare_fields(
+            path,
+            mapping,
+            problems,
+            (0..before.field_len().max(after.field_len()))
+                .map(|index| (format!(".{index}"), before.field(index), after.field(index)))
+                .collect(),
+        ),
+        (ReflectRef::List(before), ReflectRef::List(after)) => compare_fields(
+            path,
+            mapping,
+            problems,
+            (0..before.len().max(after.len()))
+                .map(|index| (format!("[{index}]"), before.get(index), after.get(index)))
+                .collect(),
+        ),
+        (ReflectRef::Array(before), ReflectRef::Array(after)) => compare_fields(
+            path,
+            mapping,
+            problems,
+            (0..before.len().max(after.len()))
+                .map(|index| (format!("[{index}]"), before.get(index), after.get(index)))
+                .collect(),
+        ),
+        (ReflectRef::Enum(before), ReflectRef::Enum(after))
+            if before.variant_name() == after.variant_name() =>
+        {
+            compare_fields(
+                path,
+                mapping,
+                problems,
+                (0..before.field_len())
+                    .map(|index| {
+                        let field = match before.name_at(index) {
+                            Some(name) => format!("::{}.{name}", before.variant_name()),
+                            None => format!("::{}.{index}", before.variant_name()),
+                        };
+                        (field, before.field_at(index), after.field_at(index))
+                    })
+                    .collect(),
+            )
+        }
+        // types without `PartialEq` reflection can't be compared
+        _ => {
+            if before.reflect_partial_eq(after) == Some(false) {
+                problems.push(format!("{path} changed from {before:?} to {after:?}"));
+            }
+        }
+    }
+}
+
+type FieldPair<'a> = (
+    String,
+    Option<&'a dyn PartialReflect>,
+    Option<&'a dyn PartialReflect>,
+);
+
+fn compare_fields(
+    path: &str,
+    mapping: &EntityHashMap<Entity>,
+    problems: &mut Vec<String>,
+    fields: Vec<FieldPair<'_>>,
+) {
+    for (field, before, after) in fields {
+        match (before, after) {
+            (Some(before), Some(after)) => {
+                compare_values(&format!("{path}{field}"), before, after, mapping, problems);
+            }
+            _ => problems.push(format!("{path}{field} wasn't loaded back")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::reflect::ReflectComponent, reflect::Reflect};
+    use bevy_ecs::{component::Component, hierarchy::ChildOf};
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    #[derive(Component, Reflect, Debug, Default, PartialEq)]
+    #[reflect(Component)]
+    struct Inventory {
+        gold: u32,
+        items: Vec<String>,
+    }
+
+    #[derive(Component, Reflect, Debug, Default, PartialEq)]
+    #[reflect(Component)]
+    struct Target(#[entities] Option<Entity>);
+
+    /// forgot `#[reflect(Component)]`
+    #[derive(Component, Reflect, Debug, Default)]
+    struct Experience(u32);
+
+    /// `#[reflect(skip_serializing)]` loses the value
+    #[derive(Component, Reflect, Debug, Default)]
+    #[reflect(Component)]
+    struct Cooldown {
+        #[reflect(skip_serializing)]
+        seconds: f32,
+    }
+
+    fn save_game_app(mut app: TestApp) -> TestApp {
+        app.register_type::<ChildOf>()
+            .register_type::<Children>()
+            .register_type::<Inventory>()
+            .register_type::<Target>()
+            .register_type::<Experience>()
+            .register_type::<Cooldown>();
+        app
+    }
+
+    #[rstest]
+    fn scene_round_trip_trait_round_trip(#[from(minimal_test_app)] app: TestApp) {
+        let mut app = save_game_app(app);
+        let player = app
+            .world_mut()
+            .spawn(Inventory {
+                gold: 3,
+                items: vec!["sword".into()],
+            })
+            .id();
+        let pet = app
+            .world_mut()
+            .spawn((Target(Some(player)), ChildOf(player)))
+            .id();
+
+        let mapping = app.assert_scene_round_trip([player, pet]);
+
+        let world = app.world();
+        assert_that!(world.get_entity(player).is_ok()).is_false();
+        assert_that!(world.get::<Target>(mapping[&pet]))
+            .is_equal_to(Some(&Target(Some(mapping[&player]))));
+        assert_that!(world.get::<ChildOf>(mapping[&pet]).map(ChildOf::parent))
+            .is_equal_to(Some(mapping[&player]));
+    }
+
+    #[rstest]
+    #[should_panic(expected = "`Experience` wasn't saved, it's not registered")]
+    fn scene_round_trip_trait_unregistered_component(#[from(minimal_test_app)] app: TestApp) {
+        let mut app = save_game_app(app);
+        let player = app
+            .world_mut()
+            .spawn((Inventory::default(), Experience(10)))
+            .id();
+
+        app.assert_scene_round_trip([player]);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "wasn't saved, but despawned with its parent")]
+    fn scene_round_trip_trait_unsaved_child(#[from(minimal_test_app)] app: TestApp) {
+        let mut app = save_game_app(app);
+        let player = app.world_mut().spawn(Inventory::default()).id();
+        app.world_mut().spawn((Inventory::default(), ChildOf(player)));
+
+        app.assert_scene_round_trip([player]);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "Cooldown.seconds changed from 1.5 to 0.0")]
+    fn scene_round_trip_trait_lost_field(#[from(minimal_test_app)] app: TestApp) {
+        let mut app = save_game_app(app);
+        let player = app.world_mut().spawn(Cooldown { seconds: 1.5 }).id();
+
+        app.assert_scene_round_trip([player]);
+    }
+
+    #[rstest]
+    fn scene_round_trip_trait_ron(#[from(minimal_test_app)] app: TestApp) {
+        let mut app = save_game_app(app);
+        let player = app.world_mut().spawn(Inventory::default()).id();
+
+        let ron = app.to_scene_ron([player]).unwrap();
+
+        assert_that!(ron).contains("Inventory");
+        assert_that!(app.load_scene_ron(&ron).map(|mapping| mapping.len())).is_ok_containing(1);
+    }
+}
@@ -0,0 +1,150 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use bevy_ecs::{bundle::Bundle, entity::Entity, hierarchy::ChildOf, name::Name};
+
+use crate::as_world::AsWorld;
+
+#[doc = include_str!("./spawn_test_entities.md")]
+pub trait SpawnTestEntities {
+    fn spawn_named(&mut self, name: impl Into<Cow<'static, str>>, bundle: impl Bundle) -> Entity;
+    fn entity_by_name(&self, name: &str) -> Option<Entity>;
+}
+
+impl<T: AsWorld> SpawnTestEntities for T {
+    fn spawn_named(&mut self, name: impl Into<Cow<'static, str>>, bundle: impl Bundle) -> Entity {
+        self.as_world_mut().spawn((Name::new(name), bundle)).id()
+    }
+
+    fn entity_by_name(&self, name: &str) -> Option<Entity> {
+        let world = self.as_world();
+        world
+            .try_query::<(Entity, &Name)>()?
+            .iter(world)
+            .find(|(_, entity_name)| entity_name.as_str() == name)
+            .map(|(entity, _)| entity)
+    }
+}
+
+/// Spawns a tree of named entities and returns a map from their names to
+/// their [`Entity`].
+///
+/// ```rust,ignore
+/// let entities = spawn_hierarchy!(app, {
+///     "root": Transform::default() => {
+///         "arm": Transform::from_xyz(1.0, 0.0, 0.0) => {
+///             "hand": (),
+///         },
+///         "leg": (),
+///     },
+/// });
+/// ```
+#[macro_export]
+macro_rules! spawn_hierarchy {
+    ($app:expr, { $($tree:tt)* }) => {{
+        let mut entities = ::std::collections::HashMap::new();
+        $crate::__spawn_hierarchy_nodes!($app, entities, ::core::option::Option::None, $($tree)*);
+        entities
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __spawn_hierarchy_nodes {
+    ($app:expr, $entities:ident, $parent:expr $(,)?) => {};
+    (
+        $app:expr, $entities:ident, $parent:expr,
+        $name:literal : $bundle:expr => { $($children:tt)* } $(, $($rest:tt)*)?
+    ) => {
+        {
+            let entity = $crate::prelude::spawn_hierarchy_node(
+                &mut $app, &mut $entities, $parent, $name, $bundle,
+            );
+            $crate::__spawn_hierarchy_nodes!(
+                $app, $entities, ::core::option::Option::Some(entity), $($children)*
+            );
+        }
+        $crate::__spawn_hierarchy_nodes!($app, $entities, $parent $(, $($rest)*)?);
+    };
+    (
+        $app:expr, $entities:ident, $parent:expr,
+        $name:literal : $bundle:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::prelude::spawn_hierarchy_node(&mut $app, &mut $entities, $parent, $name, $bundle);
+        $crate::__spawn_hierarchy_nodes!($app, $entities, $parent $(, $($rest)*)?);
+    };
+}
+
+#[doc(hidden)]
+pub fn spawn_hierarchy_node(
+    app: &mut impl AsWorld,
+    entities: &mut HashMap<String, Entity>,
+    parent: Option<Entity>,
+    name: &'static str,
+    bundle: impl Bundle,
+) -> Entity {
+    let mut entity = app.as_world_mut().spawn((Name::new(name), bundle));
+    if let Some(parent) = parent {
+        entity.insert(ChildOf(parent));
+    }
+    let entity = entity.id();
+    if entities.insert(name.to_string(), entity).is_some() {
+        panic!("spawn_hierarchy! used the name {name:?} twice");
+    }
+    entity
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{component::Component, hierarchy::Children};
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Length(u32);
+
+    #[rstest]
+    fn spawn_test_entities_trait_spawn_named(#[from(minimal_test_app)] mut app: TestApp) {
+        let entity = app.spawn_named("player", Length(2));
+
+        assert_that!(app.entity_by_name("player")).is_equal_to(Some(entity));
+        assert_that!(app.entity_by_name("enemy")).is_none();
+        assert_that!(app.world().get::<Length>(entity)).is_equal_to(Some(&Length(2)));
+    }
+
+    #[rstest]
+    fn spawn_test_entities_spawn_hierarchy(#[from(minimal_test_app)] mut app: TestApp) {
+        let entities = spawn_hierarchy!(app, {
+            "root": () => {
+                "arm": Length(3) => {
+                    "hand": Length(1),
+                },
+                "leg": Length(4),
+            },
+            "other root": (),
+        });
+
+        assert_that!(entities).has_length(5);
+        let children = |name: &str| {
+            app.world()
+                .get::<Children>(entities[name])
+                .map(|children| children.to_vec())
+                .unwrap_or_default()
+        };
+        assert_that!(children("root")).is_equal_to(vec![entities["arm"], entities["leg"]]);
+        assert_that!(children("arm")).is_equal_to(vec![entities["hand"]]);
+        assert_that!(children("other root")).is_empty();
+        assert_that!(app.world().get::<ChildOf>(entities["root"])).is_none();
+        assert_that!(app.entity_by_name("hand")).is_equal_to(Some(entities["hand"]));
+    }
+
+    #[rstest]
+    #[should_panic(expected = "used the name \"twin\" twice")]
+    fn spawn_test_entities_spawn_hierarchy_duplicate_name(
+        #[from(minimal_test_app)] mut app: TestApp,
+    ) {
+        spawn_hierarchy!(app, { "twin": (), "twin": () });
+    }
+}
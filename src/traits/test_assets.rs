@@ -0,0 +1,187 @@
+use std::path::Path;
+
+use bevy_asset::{
+    Asset, AssetPath, AssetServer, Handle, LoadState, RecursiveDependencyLoadState,
+    UntypedAssetId, io::memory::Value,
+};
+use bevy_ecs::world::World;
+
+use crate::{
+    as_world::AsWorld,
+    assets::{AssetWaitError, TestAssetSource, let_tasks_run},
+};
+
+const NO_SOURCE: &str = "no in-memory asset source, use `asset_test_app` or add `TestAssetsPlugin`";
+
+#[doc = include_str!("./test_assets.md")]
+pub trait TestAssets {
+    fn add_test_asset(&mut self, path: impl AsRef<Path>, bytes: impl Into<Value>) -> &mut Self;
+    fn modify_test_asset(&mut self, path: impl AsRef<Path>, bytes: impl Into<Value>) -> &mut Self;
+    fn update_until_loaded(&mut self, id: impl Into<UntypedAssetId>, max_frames: u32)
+    -> LoadState;
+    fn load_and_wait<A: Asset>(
+        &mut self,
+        path: impl Into<AssetPath<'static>>,
+        max_frames: u32,
+    ) -> Result<Handle<A>, AssetWaitError>;
+    fn wait_for_all_assets(&mut self, max_frames: u32) -> Result<(), AssetWaitError>;
+}
+
+/// Runs the loading tasks, then an update to take in what they loaded.
+fn update_loading(app: &mut impl AsWorld) {
+    let_tasks_run();
+    app.as_sub_apps_mut()
+        .expect("waiting for assets needs an app to update")
+        .update();
+}
+
+/// the paths read from [`TestAssetSource`] that are still loading, or the
+/// first that failed
+fn loading_test_assets(world: &World) -> Result<Vec<String>, AssetWaitError> {
+    let server = world.resource::<AssetServer>();
+    let mut loading = Vec::new();
+    for path in world
+        .get_resource::<TestAssetSource>()
+        .expect(NO_SOURCE)
+        .paths()
+    {
+        let path = AssetPath::from_path(&path).into_owned();
+        for handle in server.get_handles_untyped(&path) {
+            match server.recursive_dependency_load_state(&handle) {
+                RecursiveDependencyLoadState::Failed(error) => {
+                    return Err(AssetWaitError::Failed {
+                        path: path.to_string(),
+                        error,
+                    });
+                }
+                RecursiveDependencyLoadState::Loading => loading.push(path.to_string()),
+                RecursiveDependencyLoadState::NotLoaded | RecursiveDependencyLoadState::Loaded => {}
+            }
+        }
+    }
+    Ok(loading)
+}
+
+impl<T: AsWorld> TestAssets for T {
+    fn add_test_asset(&mut self, path: impl AsRef<Path>, bytes: impl Into<Value>) -> &mut Self {
+        self.as_world()
+            .get_resource::<TestAssetSource>()
+            .expect(NO_SOURCE)
+            .insert(path.as_ref(), bytes);
+        self
+    }
+
+    fn modify_test_asset(&mut self, path: impl AsRef<Path>, bytes: impl Into<Value>) -> &mut Self {
+        self.as_world()
+            .get_resource::<TestAssetSource>()
+            .expect(NO_SOURCE)
+            .modify(path.as_ref(), bytes);
+        self
+    }
+
+    fn update_until_loaded(
+        &mut self,
+        id: impl Into<UntypedAssetId>,
+        max_frames: u32,
+    ) -> LoadState {
+        let id = id.into();
+        let load_state = |app: &Self| app.as_world().resource::<AssetServer>().load_state(id);
+        for _ in 0..max_frames {
+            if matches!(load_state(self), LoadState::Loaded | LoadState::Failed(_)) {
+                break;
+            }
+            update_loading(self);
+        }
+        load_state(self)
+    }
+
+    fn load_and_wait<A: Asset>(
+        &mut self,
+        path: impl Into<AssetPath<'static>>,
+        max_frames: u32,
+    ) -> Result<Handle<A>, AssetWaitError> {
+        let path = path.into();
+        let handle = self
+            .as_world()
+            .resource::<AssetServer>()
+            .load::<A>(path.clone());
+        match self.update_until_loaded(&handle, max_frames) {
+            LoadState::Loaded => Ok(handle),
+            LoadState::Failed(error) => Err(AssetWaitError::Failed {
+                path: path.to_string(),
+                error,
+            }),
+            LoadState::NotLoaded | LoadState::Loading => Err(AssetWaitError::StillLoading {
+                paths: vec![path.to_string()],
+                frames: max_frames,
+            }),
+        }
+    }
+
+    fn wait_for_all_assets(&mut self, max_frames: u32) -> Result<(), AssetWaitError> {
+        let mut frames = 0;
+        loop {
+            // assets requested since the last update have yet to be read
+            let_tasks_run();
+            let loading = loading_test_assets(self.as_world())?;
+            if loading.is_empty() {
+                return Ok(());
+            }
+            if frames == max_frames {
+                return Err(AssetWaitError::StillLoading {
+                    paths: loading,
+                    frames,
+                });
+            }
+            update_loading(self);
+            frames += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{
+        fixtures::{asset_test_app, minimal_test_app},
+        test_app::TestApp,
+    };
+
+    #[rstest]
+    fn test_assets_trait_add_test_asset(#[from(asset_test_app)] mut app: TestApp) {
+        app.add_test_asset("levels/1.ron", b"(size: 3)")
+            .add_test_asset("levels/2.ron", "(size: 4)".as_bytes().to_vec());
+
+        let dir = &app.world().resource::<TestAssetSource>().dir;
+        assert_that!(
+            dir.get_asset(Path::new("levels/1.ron"))
+                .map(|data| data.value().to_vec())
+        )
+        .is_equal_to(Some(b"(size: 3)".to_vec()));
+        assert_that!(dir.get_asset(Path::new("levels/2.ron"))).is_some();
+    }
+
+    #[rstest]
+    #[should_panic(expected = "no in-memory asset source")]
+    fn test_assets_trait_without_plugin(#[from(minimal_test_app)] mut app: TestApp) {
+        app.add_test_asset("levels/1.ron", b"(size: 3)");
+    }
+
+    #[rstest]
+    #[should_panic(expected = "waiting for assets needs an app to update")]
+    fn test_assets_trait_wait_on_world(#[from(asset_test_app)] mut app: TestApp) {
+        app.add_test_asset("levels/1.ron", b"(size: 3)");
+        let handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load_builder()
+            .load_untyped("levels/1.ron");
+
+        app.world_mut().update_until_loaded(&handle, 100);
+    }
+}
@@ -1,6 +1,8 @@
 use bevy_ecs::message::{Message, WriteBatchIds};
 
-use crate::prelude::TestApp;
+use crate::as_world::AsWorld;
+#[cfg(feature = "scenario")]
+use crate::scenarios::{record_injected, records_injected};
 
 #[doc = include_str!("./write_messages.md")]
 pub trait WriteMessages {
@@ -12,17 +14,64 @@ pub trait WriteMessages {
     ) -> Option<WriteBatchIds<E>>;
 }
 
-impl WriteMessages for TestApp {
+impl<T: AsWorld> WriteMessages for T {
     fn write_message_default<E: Message + Default>(&mut self) {
-        self.world_mut().write_message_default::<E>();
+        self.write_message(E::default());
     }
     fn write_message<E: Message>(&mut self, message: E) {
-        self.world_mut().write_message::<E>(message);
+        let world = self.as_world_mut();
+        #[cfg(feature = "scenario")]
+        record_injected(world, &message);
+        world.write_message::<E>(message);
     }
     fn write_message_batch<E: Message>(
         &mut self,
         messages: impl IntoIterator<Item = E>,
     ) -> Option<WriteBatchIds<E>> {
-        self.world_mut().write_message_batch(messages)
+        let world = self.as_world_mut();
+        #[cfg(feature = "scenario")]
+        if records_injected::<E>(world) {
+            let messages = messages.into_iter().collect::<Vec<_>>();
+            for message in &messages {
+                record_injected(world, message);
+            }
+            return world.write_message_batch(messages);
+        }
+        world.write_message_batch(messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        message::{Message, Messages},
+        world::World,
+    };
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[derive(Clone, Debug, Default, Message, PartialEq)]
+    struct MyMessage(u32);
+
+    #[rstest]
+    fn write_messages_trait_on_world() {
+        let mut world = World::new();
+        world.init_resource::<Messages<MyMessage>>();
+
+        // `World`'s inherent methods would shadow the trait
+        WriteMessages::write_message(&mut world, MyMessage(1));
+        WriteMessages::write_message_default::<MyMessage>(&mut world);
+        WriteMessages::write_message_batch(&mut world, [MyMessage(2)]);
+
+        let messages = world.resource::<Messages<MyMessage>>();
+        assert_that!(
+            messages
+                .iter_current_update_messages()
+                .cloned()
+                .collect::<Vec<_>>()
+        )
+        .is_equal_to(vec![MyMessage(1), MyMessage(0), MyMessage(2)]);
     }
 }
@@ -0,0 +1,351 @@
+use std::{
+    any::{Any, TypeId},
+    cell::{Cell, RefCell},
+    error::Error,
+    fmt::{self, Display, Formatter},
+    iter, mem,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Once},
+};
+
+use bevy_app::App;
+use bevy_ecs::{
+    resource::Resource,
+    schedule::{
+        ApplyDeferred, FlattenedDependencies, NodeId, ScheduleBuildError, ScheduleBuildPass,
+        ScheduleGraph, Schedules, SystemKey, SystemSet, SystemSetKey, SystemWithAccess,
+        graph::DiGraph,
+    },
+    system::{Adapt, AdapterSystem, IntoSystem, RunSystemError, System},
+    world::World,
+};
+use bevy_internal::platform::hash::FixedHasher;
+use bevy_utils::DebugName;
+use indexmap::IndexSet;
+
+use crate::executor::run_single_threaded;
+
+/// A panic caught by [`TestApp::try_update`](crate::test_app::TestApp::try_update).
+///
+/// The schedules that were running are lost while unwinding, so the app can't
+/// be updated again afterwards: later calls return the same `UpdatePanic`.
+#[derive(Clone, Debug)]
+pub struct UpdatePanic {
+    pub message: String,
+    /// `file:line:column` of the panic
+    pub location: Option<String>,
+    /// the last system that started running; a panic outside of systems, e.g.
+    /// in commands applied at the end of a schedule, is blamed on it
+    ///
+    /// System names need bevy's `debug` feature, this is `None` without it.
+    pub system: Option<String>,
+    pub schedule: Option<String>,
+    /// the world's change tick after the panic
+    pub tick: u32,
+}
+
+impl Display for UpdatePanic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "panic in system `{}`",
+            self.system.as_deref().unwrap_or("<unknown>")
+        )?;
+        if let Some(schedule) = &self.schedule {
+            write!(f, " in schedule `{schedule}`")?;
+        }
+        write!(f, " at tick {}", self.tick)?;
+        if let Some(location) = &self.location {
+            write!(f, " ({location})")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl Error for UpdatePanic {}
+
+/// The panic that poisoned the app.
+#[derive(Debug, Resource)]
+pub(crate) struct CaughtUpdatePanic(pub UpdatePanic);
+
+#[derive(Clone, Debug)]
+struct RunningSystem {
+    schedule: Arc<str>,
+    system: Option<Arc<str>>,
+}
+
+struct PanicRecord {
+    location: Option<String>,
+    running: Option<RunningSystem>,
+}
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// set by the systems [`RecordRunningSystem`] wrapped, right before they run
+    static RUNNING: RefCell<Option<RunningSystem>> = const { RefCell::new(None) };
+    static LAST_PANIC: RefCell<Option<PanicRecord>> = const { RefCell::new(None) };
+}
+
+/// Installed once and never removed: other threads' panics and panics outside
+/// of `try_update` are passed on to the hook that was set before.
+fn install_panic_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                // the panic is reported through `UpdatePanic` instead
+                LAST_PANIC.set(Some(PanicRecord {
+                    location: info.location().map(ToString::to_string),
+                    running: RUNNING.with_borrow(Clone::clone),
+                }));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
+
+/// Marks the schedules that got a [`RecordRunningSystem`] pass; configuring
+/// it also makes built schedules rebuild.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+struct RecordedSchedule;
+
+/// Wraps every system so that it records itself as running on the current
+/// thread, right before it runs.
+///
+/// The wrapped systems report no access, which only the multi-threaded
+/// executor and the ambiguity detection need: both are off for schedules that
+/// `try_update` runs.
+#[derive(Debug)]
+struct RecordRunningSystem {
+    schedule: Arc<str>,
+}
+
+type RecordedSystem = AdapterSystem<RecordRunning, SystemWithAccess>;
+
+/// Sets [`RUNNING`] and runs the wrapped system.
+struct RecordRunning(RunningSystem);
+
+impl Adapt<SystemWithAccess> for RecordRunning {
+    type In = ();
+    type Out = ();
+
+    fn adapt(
+        &mut self,
+        (): (),
+        run_system: impl FnOnce(()) -> Result<(), RunSystemError>,
+    ) -> Result<(), RunSystemError> {
+        RUNNING.set(Some(self.0.clone()));
+        run_system(())
+    }
+}
+
+/// the system's name, unless bevy's `debug` feature is off and every name is
+/// the same placeholder
+fn known_name(name: &DebugName) -> Option<Arc<str>> {
+    let names_enabled = DebugName::borrowed("a") != DebugName::borrowed("b");
+    names_enabled.then(|| name.to_string().into())
+}
+
+impl ScheduleBuildPass for RecordRunningSystem {
+    type EdgeOptions = ();
+
+    fn add_dependency(&mut self, _from: NodeId, _to: NodeId, _options: Option<&()>) {}
+
+    fn collapse_set(
+        &mut self,
+        _set: SystemSetKey,
+        _systems: &IndexSet<SystemKey, FixedHasher>,
+        _dependency_flattening: &DiGraph<NodeId>,
+    ) -> impl Iterator<Item = (NodeId, NodeId)> {
+        iter::empty()
+    }
+
+    fn build(
+        &mut self,
+        _world: &mut World,
+        graph: &mut ScheduleGraph,
+        _dependency_flattened: FlattenedDependencies<'_>,
+    ) -> Result<(), ScheduleBuildError> {
+        let new_systems: Vec<_> = graph
+            .systems
+            .iter()
+            .filter(|(_, system, _)| {
+                // the executor recognizes sync points by their type
+                let system_type = system.system_type();
+                system_type != TypeId::of::<RecordedSystem>()
+                    && system_type != TypeId::of::<ApplyDeferred>()
+            })
+            .map(|(key, _, _)| key)
+            .collect();
+        for key in new_systems {
+            let Some(slot) = graph.systems.get_mut(key) else {
+                continue;
+            };
+            let name = slot.name();
+            let running = RunningSystem {
+                schedule: self.schedule.clone(),
+                system: known_name(&name),
+            };
+            let placeholder = SystemWithAccess::new(Box::new(IntoSystem::into_system(|| {})));
+            let system = mem::replace(slot, placeholder);
+            *slot = SystemWithAccess::new(Box::new(RecordedSystem::new(
+                RecordRunning(running),
+                system,
+                name,
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn record_running_systems(world: &mut World) {
+    let mut schedules = world.get_resource_or_init::<Schedules>();
+    for (label, schedule) in schedules.iter_mut() {
+        if !schedule.graph().system_sets.contains(RecordedSchedule) {
+            schedule.add_build_pass(RecordRunningSystem {
+                schedule: format!("{label:?}").into(),
+            });
+            schedule.configure_sets(RecordedSchedule);
+        }
+    }
+}
+
+/// Every schedule runs single-threaded from here on, so that the system that
+/// panicked is the last one recorded on the test thread.
+pub(crate) fn try_update(app: &mut App) -> Result<(), UpdatePanic> {
+    if let Some(caught) = app.world().get_resource::<CaughtUpdatePanic>() {
+        return Err(caught.0.clone());
+    }
+    install_panic_hook();
+    run_single_threaded(app.world_mut());
+    record_running_systems(app.world_mut());
+
+    catch_update(app).inspect_err(|update_panic| {
+        app.insert_resource(CaughtUpdatePanic(update_panic.clone()));
+    })
+}
+
+/// Runs an update and returns its panic, if any, without changing how the app
+/// runs. Only the message is known unless [`try_update`] ran before.
+pub(crate) fn catch_update(app: &mut App) -> Result<(), UpdatePanic> {
+    RUNNING.set(None);
+    LAST_PANIC.set(None);
+    CATCHING.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| app.update()));
+    CATCHING.set(false);
+
+    let Err(payload) = result else {
+        return Ok(());
+    };
+    let record = LAST_PANIC.take();
+    let running = record.as_ref().and_then(|record| record.running.as_ref());
+    Err(UpdatePanic {
+        message: payload_message(payload.as_ref()),
+        location: record.as_ref().and_then(|record| record.location.clone()),
+        system: running.and_then(|running| running.system.as_deref().map(str::to_string)),
+        schedule: running.map(|running| running.schedule.to_string()),
+        tick: app.world().read_change_tick().get(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{PostUpdate, Update};
+    use bevy_ecs::{schedule::IntoScheduleConfigs, system::Commands};
+    use rstest::rstest;
+    use speculoos::prelude::*;
+
+    use super::*;
+    use crate::{fixtures::minimal_test_app, test_app::TestApp};
+
+    fn assert_system(update_panic: &UpdatePanic, system: &str) {
+        // without bevy's `debug` feature systems have no names
+        let names_enabled = known_name(&DebugName::borrowed("")).is_some();
+        assert_that!(update_panic.system.as_deref())
+            .named("system")
+            .matches(|name| match name {
+                Some(name) => name.ends_with(system),
+                None => !names_enabled,
+            });
+    }
+
+    fn panicking_system() {
+        panic!("oh no");
+    }
+
+    #[rstest]
+    fn test_try_update_ok(#[from(minimal_test_app)] mut app: TestApp) {
+        assert_that!(app.try_update()).is_ok();
+    }
+
+    #[rstest]
+    fn test_try_update_reports_panicking_system(#[from(minimal_test_app)] mut app: TestApp) {
+        app.add_systems(Update, panicking_system);
+
+        let update_panic = app.try_update().unwrap_err();
+
+        assert_that!(update_panic.message.as_str()).is_equal_to("oh no");
+        assert_system(&update_panic, "panicking_system");
+        assert_that!(update_panic.schedule.as_deref())
+            .named("schedule")
+            .is_equal_to(Some("Update"));
+        assert_that!(update_panic.location.as_deref())
+            .named("location")
+            .matches(|location| location.is_some_and(|l| l.contains("update_panic.rs")));
+    }
+
+    #[rstest]
+    fn test_try_update_reports_system_of_built_schedule(
+        #[from(minimal_test_app)] mut app: TestApp,
+    ) {
+        app.update();
+        app.add_systems(PostUpdate, panicking_system);
+
+        let update_panic = app.try_update().unwrap_err();
+
+        assert_system(&update_panic, "panicking_system");
+        assert_that!(update_panic.schedule.as_deref())
+            .named("schedule")
+            .is_equal_to(Some("PostUpdate"));
+    }
+
+    fn queue_panicking_command(mut commands: Commands) {
+        commands.queue(|_: &mut World| panic!("oh no"));
+    }
+
+    fn skipped_system() {}
+
+    #[rstest]
+    fn test_try_update_ignores_skipped_systems(#[from(minimal_test_app)] mut app: TestApp) {
+        app.add_systems(
+            Update,
+            (queue_panicking_command, skipped_system.run_if(|| false)).chain_ignore_deferred(),
+        );
+
+        let update_panic = app.try_update().unwrap_err();
+
+        assert_system(&update_panic, "queue_panicking_command");
+    }
+
+    #[rstest]
+    fn test_try_update_after_panic(#[from(minimal_test_app)] mut app: TestApp) {
+        app.add_systems(Update, panicking_system);
+
+        let first = app.try_update().unwrap_err();
+        let second = app.try_update().unwrap_err();
+
+        assert_that!(second.to_string()).is_equal_to(first.to_string());
+    }
+}